@@ -5,8 +5,12 @@ use rdml_leptos::rdml;
 fn App() -> impl IntoView {
     let items = RwSignal::new(vec!["Item 1".to_owned(), "Item 2".into()]);
     let value = RwSignal::new("".to_owned());
+    let notice = "<b>Welcome</b><script>alert(1)</script>".to_owned();
 
     rdml! {
+        #[sanitize]
+        raw(notice)
+
         ol {
             #[key(item.clone())]
             for (i, item) in items.get().into_iter().enumerate() {
@@ -27,7 +31,7 @@ fn App() -> impl IntoView {
                 value.set("".to_owned());
             }
         ) {
-            input(bind:value=value) {}
+            input(bind:value=value);
             button { "Add Item" }
         }
 
@@ -1,12 +1,14 @@
 use syn::{
-    Expr, Ident, LitStr, Path, Result, Token,
+    Expr, Ident, LitStr, Path, Result, Token, braced, bracketed,
     ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    token::Paren,
+    token::{Brace, Bracket, Paren},
 };
 
+use crate::Block;
+
 /// An attribute name with a directive: `on:click`
 #[derive(Debug, PartialEq, Hash)]
 pub struct AttributeNameDirective {
@@ -50,29 +52,313 @@ impl Parse for AttributeName {
     }
 }
 
+/// An entry of a [`ClassList`]: `"active" if selected`
+#[derive(Debug, PartialEq, Hash)]
+pub struct ClassListEntry {
+    pub class: Expr,
+    pub condition: Option<(Token![if], Expr)>,
+}
+
+impl Parse for ClassListEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let class = input.parse()?;
+        let condition = if input.peek(Token![if]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        Ok(Self { class, condition })
+    }
+}
+
+/// A list of classes, each of which can be conditional: `["btn", "btn-primary" if primary]`
+#[derive(Debug, PartialEq, Hash)]
+pub struct ClassList {
+    pub bracket_token: Bracket,
+    pub entries: Punctuated<ClassListEntry, Token![,]>,
+}
+
+impl Parse for ClassList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            bracket_token: bracketed!(content in input),
+            entries: content.parse_terminated(ClassListEntry::parse, Token![,])?,
+        })
+    }
+}
+
+/// The name of a [`StyleProperty`], in kebab-case: `margin-top`
+#[derive(Debug, PartialEq, Hash)]
+pub struct StylePropertyName {
+    pub segments: Punctuated<Ident, Token![-]>,
+}
+
+impl StylePropertyName {
+    /// The name of the css property: `"margin-top"`
+    pub fn value(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.unraw().to_string())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
+
+impl Parse for StylePropertyName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut segments = Punctuated::new();
+        segments.push_value(input.call(Ident::parse_any)?);
+        while input.peek(Token![-]) {
+            segments.push_punct(input.parse()?);
+            segments.push_value(input.call(Ident::parse_any)?);
+        }
+        Ok(Self { segments })
+    }
+}
+
+/// A property of a [`StyleObject`], which can be conditional: `display: "none" if hidden`
+#[derive(Debug, PartialEq, Hash)]
+pub struct StyleProperty {
+    pub name: StylePropertyName,
+    pub colon_token: Token![:],
+    pub value: Expr,
+    pub condition: Option<(Token![if], Expr)>,
+}
+
+impl Parse for StyleProperty {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let colon_token = input.parse()?;
+        let value = input.parse()?;
+        let condition = if input.peek(Token![if]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        Ok(Self {
+            name,
+            colon_token,
+            value,
+            condition,
+        })
+    }
+}
+
+/// A list of css declarations: `{ color: "red", margin-top: (px(gap)) }`
+#[derive(Debug, PartialEq, Hash)]
+pub struct StyleObject {
+    pub brace_token: Brace,
+    pub properties: Punctuated<StyleProperty, Token![,]>,
+}
+
+impl Parse for StyleObject {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            brace_token: braced!(content in input),
+            properties: content.parse_terminated(StyleProperty::parse, Token![,])?,
+        })
+    }
+}
+
+/// The value of an [`Attribute`]
+#[derive(Debug, PartialEq, Hash)]
+pub enum AttributeValue {
+    /// A rust expression: `"value"` or `count + 1`
+    Expr(Expr),
+
+    /// Markup, for props taking a view: `{ span { "Loading" } }`
+    Markup(Block),
+
+    /// A list of classes, for `class` attributes: `["btn", "active" if selected]`
+    ClassList(ClassList),
+
+    /// A list of css declarations, for `style` attributes: `{ color: "red" }`
+    Style(StyleObject),
+}
+
+impl Parse for AttributeValue {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Brace) {
+            Ok(Self::Markup(input.parse()?))
+        } else {
+            Ok(Self::Expr(input.parse()?))
+        }
+    }
+}
+
 /// An attribute: `class="value"`
+///
+/// A value surrounded by braces is markup rather than a block expression, a block expression can
+/// be wrapped in parentheses instead: `value=({ let a = 1; a })`. Likewise the value of a `class`
+/// attribute surrounded by brackets is a [`ClassList`] rather than an array expression, and the
+/// value of a `style` attribute surrounded by braces is a [`StyleObject`] rather than markup.
+///
+/// An attribute whose name is followed by a `?` is optional (`title?=maybe_title`), its value is
+/// an `Option` and the attribute is left out while it is `None`. How it is lowered is up to the
+/// backend.
+///
+/// The value of an attribute can be omitted, in which case `eq_token` and `value` are both
+/// `None`:
+///  - An attribute with a single identifier as its name is punned, using a variable of the same
+///    name (`title` is the same as `title=title`)
+///  - An attribute with a quoted or directive name is boolean (`"readonly"` or `prop:checked`),
+///    how it is lowered is up to the backend
 #[derive(Debug, PartialEq, Hash)]
 pub struct Attribute {
+    pub attrs: Vec<syn::Attribute>,
     pub name: AttributeName,
-    pub eq_token: Token![=],
-    pub value: Expr,
+    pub question_token: Option<Token![?]>,
+    pub eq_token: Option<Token![=]>,
+    pub value: Option<AttributeValue>,
+}
+
+impl Attribute {
+    /// Whether the attribute is left out while its value is `None`: `title?=maybe_title`
+    pub fn is_optional(&self) -> bool {
+        self.question_token.is_some()
+    }
+
+    /// Whether the value was omitted in favor of a variable with the same name as the attribute
+    pub fn is_punned(&self) -> bool {
+        self.value.is_none() && matches!(self.name, AttributeName::Single(_))
+    }
+
+    /// Whether the value of an attribute with a quoted or directive name was omitted
+    pub fn is_boolean(&self) -> bool {
+        self.value.is_none() && !matches!(self.name, AttributeName::Single(_))
+    }
 }
 
 impl Parse for Attribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let name = input.parse()?;
+        let question_token: Option<Token![?]> = input.parse()?;
+        let can_omit_value = question_token.is_none()
+            && match &name {
+                AttributeName::Single(path) => path.get_ident().is_some(),
+                AttributeName::Quoted(_) | AttributeName::Directive(_) => true,
+            };
+        if can_omit_value && !input.peek(Token![=]) {
+            return Ok(Self {
+                attrs,
+                name,
+                question_token,
+                eq_token: None,
+                value: None,
+            });
+        }
+
+        let eq_token = Some(input.parse()?);
+        let value = match &name {
+            AttributeName::Single(path) if path.is_ident("class") && input.peek(Bracket) => {
+                AttributeValue::ClassList(input.parse()?)
+            }
+            AttributeName::Single(path) if path.is_ident("style") && input.peek(Brace) => {
+                AttributeValue::Style(input.parse()?)
+            }
+            _ => input.parse()?,
+        };
+        Ok(Self {
+            attrs,
+            name,
+            question_token,
+            eq_token,
+            value: Some(value),
+        })
+    }
+}
+
+/// A spread attribute, forwarding a collection of attributes: `..attrs`
+#[derive(Debug, PartialEq, Hash)]
+pub struct SpreadAttribute {
+    pub attrs: Vec<syn::Attribute>,
+    pub dot2_token: Token![..],
+    pub expr: Expr,
+}
+
+impl Parse for SpreadAttribute {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
-            name: input.parse()?,
-            eq_token: input.parse()?,
-            value: input.parse()?,
+            attrs: input.call(syn::Attribute::parse_outer)?,
+            dot2_token: input.parse()?,
+            expr: input.parse()?,
         })
     }
 }
 
-/// An list of attributes: `(class="value", id="value")`
+/// A group of attributes which are only added while a condition holds:
+/// `if open { "aria-expanded"="true", role="menu" }`
+#[derive(Debug, PartialEq, Hash)]
+pub struct ConditionalAttributes {
+    pub attrs: Vec<syn::Attribute>,
+    pub if_token: Token![if],
+    pub cond: Expr,
+    pub brace_token: Brace,
+    pub attributes: Punctuated<AttributeEntry, Token![,]>,
+}
+
+impl Parse for ConditionalAttributes {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            attrs: input.call(syn::Attribute::parse_outer)?,
+            if_token: input.parse()?,
+            cond: input.call(Expr::parse_without_eager_brace)?,
+            brace_token: braced!(content in input),
+            attributes: content.parse_terminated(AttributeEntry::parse, Token![,])?,
+        })
+    }
+}
+
+/// An entry of an attribute list
+///
+/// Every entry can be preceded by outer attributes, e.g. `#[cfg(debug_assertions)]`, which are
+/// left to backends to interpret.
+#[derive(Debug, PartialEq, Hash)]
+pub enum AttributeEntry {
+    /// An attribute: `class="value"`
+    Attribute(Attribute),
+
+    /// A spread attribute: `..attrs`
+    Spread(SpreadAttribute),
+
+    /// A conditional group of attributes: `if open { role="menu" }`
+    Conditional(ConditionalAttributes),
+}
+
+impl AttributeEntry {
+    /// The outer attributes of the entry
+    pub fn attrs(&self) -> &[syn::Attribute] {
+        match self {
+            AttributeEntry::Attribute(attribute) => &attribute.attrs,
+            AttributeEntry::Spread(spread) => &spread.attrs,
+            AttributeEntry::Conditional(conditional) => &conditional.attrs,
+        }
+    }
+}
+
+impl Parse for AttributeEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        fork.call(syn::Attribute::parse_outer)?;
+        if fork.peek(Token![..]) {
+            Ok(AttributeEntry::Spread(input.parse()?))
+        } else if fork.peek(Token![if]) {
+            Ok(AttributeEntry::Conditional(input.parse()?))
+        } else {
+            Ok(AttributeEntry::Attribute(input.parse()?))
+        }
+    }
+}
+
+/// An list of attributes: `(class="value", id="value", ..attrs)`
 #[derive(Debug, PartialEq, Hash)]
 pub struct Attributes {
     pub paren_token: Paren,
-    pub attributes: Punctuated<Attribute, Token![,]>,
+    pub attributes: Punctuated<AttributeEntry, Token![,]>,
 }
 
 impl Parse for Attributes {
@@ -80,7 +366,135 @@ impl Parse for Attributes {
         let content;
         Ok(Self {
             paren_token: parenthesized!(content in input),
-            attributes: content.parse_terminated(Attribute::parse, Token![,])?,
+            attributes: content.parse_terminated(AttributeEntry::parse, Token![,])?,
         })
     }
 }
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt};
+
+    use super::*;
+
+    impl ToTokens for AttributeNameDirective {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.directive.to_tokens(tokens);
+            self.colon_token.to_tokens(tokens);
+            self.path.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for AttributeName {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                AttributeName::Single(path) => path.to_tokens(tokens),
+                AttributeName::Quoted(lit_str) => lit_str.to_tokens(tokens),
+                AttributeName::Directive(directive) => directive.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for AttributeValue {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                AttributeValue::Expr(expr) => expr.to_tokens(tokens),
+                AttributeValue::Markup(block) => block.to_tokens(tokens),
+                AttributeValue::ClassList(class_list) => class_list.to_tokens(tokens),
+                AttributeValue::Style(style) => style.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for StylePropertyName {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.segments.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for StyleProperty {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.name.to_tokens(tokens);
+            self.colon_token.to_tokens(tokens);
+            self.value.to_tokens(tokens);
+            if let Some((if_token, condition)) = &self.condition {
+                if_token.to_tokens(tokens);
+                condition.to_tokens(tokens);
+            }
+        }
+    }
+
+    impl ToTokens for StyleObject {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.brace_token.surround(tokens, |tokens| {
+                self.properties.to_tokens(tokens);
+            });
+        }
+    }
+
+    impl ToTokens for ClassListEntry {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.class.to_tokens(tokens);
+            if let Some((if_token, condition)) = &self.condition {
+                if_token.to_tokens(tokens);
+                condition.to_tokens(tokens);
+            }
+        }
+    }
+
+    impl ToTokens for ClassList {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.bracket_token.surround(tokens, |tokens| {
+                self.entries.to_tokens(tokens);
+            });
+        }
+    }
+
+    impl ToTokens for Attribute {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.name.to_tokens(tokens);
+            self.question_token.to_tokens(tokens);
+            self.eq_token.to_tokens(tokens);
+            self.value.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for SpreadAttribute {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.dot2_token.to_tokens(tokens);
+            self.expr.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for AttributeEntry {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                AttributeEntry::Attribute(attribute) => attribute.to_tokens(tokens),
+                AttributeEntry::Spread(spread) => spread.to_tokens(tokens),
+                AttributeEntry::Conditional(conditional) => conditional.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for ConditionalAttributes {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.if_token.to_tokens(tokens);
+            self.cond.to_tokens(tokens);
+            self.brace_token.surround(tokens, |tokens| {
+                self.attributes.to_tokens(tokens);
+            });
+        }
+    }
+
+    impl ToTokens for Attributes {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.paren_token.surround(tokens, |tokens| {
+                self.attributes.to_tokens(tokens);
+            });
+        }
+    }
+}
@@ -23,6 +23,9 @@ impl Parse for Block {
     }
 }
 
+/// The top level nodes of a template
+///
+/// Parse a [`Recovered<Nodes>`](crate::Recovered) instead to report every syntax error at once.
 #[derive(Debug, PartialEq, Hash)]
 pub struct Nodes {
     pub nodes: Vec<Node>,
@@ -35,3 +38,25 @@ impl Parse for Nodes {
         })
     }
 }
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt};
+
+    use super::*;
+
+    impl ToTokens for Block {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.brace_token.surround(tokens, |tokens| {
+                tokens.append_all(&self.nodes);
+            });
+        }
+    }
+
+    impl ToTokens for Nodes {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.nodes);
+        }
+    }
+}
@@ -1,10 +1,125 @@
+use std::fmt::{self, Display};
+
 use syn::{
-    Path, Result,
+    Error, Ident, LitInt, Pat, Path, Result, Token,
+    ext::IdentExt,
     parse::{Parse, ParseStream},
-    token::Paren,
+    punctuated::Punctuated,
+    token::{Brace, Paren},
 };
 
-use crate::{Attributes, Block};
+use crate::{Attributes, Block, helpers::parse_pat_with_type};
+
+/// A segment of a [`SelectorName`]: `card` or `4`
+#[derive(Debug, PartialEq, Hash)]
+pub enum SelectorNameSegment {
+    Ident(Ident),
+    Int(LitInt),
+}
+
+impl Parse for SelectorNameSegment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitInt) {
+            Ok(Self::Int(input.parse()?))
+        } else {
+            Ok(Self::Ident(input.call(Ident::parse_any)?))
+        }
+    }
+}
+
+/// A `-` separated class or id name in a selector shorthand: `card`, `is-active` or `mt-4`
+#[derive(Debug, PartialEq, Hash)]
+pub struct SelectorName {
+    pub segments: Punctuated<SelectorNameSegment, Token![-]>,
+}
+
+impl Parse for SelectorName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut segments = Punctuated::new();
+        segments.push_value(input.parse()?);
+        while input.peek(Token![-]) && (input.peek2(Ident::peek_any) || input.peek2(LitInt)) {
+            segments.push_punct(input.parse()?);
+            segments.push_value(input.parse()?);
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl Display for SelectorName {
+    /// Writes the name as it appears in css: `is-active`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index > 0 {
+                f.write_str("-")?;
+            }
+            match segment {
+                SelectorNameSegment::Ident(ident) => write!(f, "{}", ident.unraw())?,
+                SelectorNameSegment::Int(lit_int) => write!(f, "{lit_int}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A class in a selector shorthand: `.card`
+#[derive(Debug, PartialEq, Hash)]
+pub struct ElementClass {
+    pub dot_token: Token![.],
+    pub name: SelectorName,
+}
+
+impl Parse for ElementClass {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            dot_token: input.parse()?,
+            name: input.parse()?,
+        })
+    }
+}
+
+/// An id in a selector shorthand: `#main`
+#[derive(Debug, PartialEq, Hash)]
+pub struct ElementId {
+    pub pound_token: Token![#],
+    pub name: SelectorName,
+}
+
+impl Parse for ElementId {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            pound_token: input.parse()?,
+            name: input.parse()?,
+        })
+    }
+}
+
+/// The parameters a component passes to its children: `|row, index: usize|`
+#[derive(Debug, PartialEq, Hash)]
+pub struct ChildrenParams {
+    pub or1_token: Token![|],
+    /// The patterns of the parameters, a [`Pat::Type`] if the parameter has a type annotation
+    pub inputs: Punctuated<Pat, Token![,]>,
+    pub or2_token: Token![|],
+}
+
+impl Parse for ChildrenParams {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let or1_token = input.parse()?;
+        let mut inputs = Punctuated::new();
+        while !input.peek(Token![|]) {
+            inputs.push_value(input.call(parse_pat_with_type)?);
+            if input.peek(Token![|]) {
+                break;
+            }
+            inputs.push_punct(input.parse()?);
+        }
+        Ok(Self {
+            or1_token,
+            inputs,
+            or2_token: input.parse()?,
+        })
+    }
+}
 
 /// An element
 ///
@@ -18,19 +133,142 @@ use crate::{Attributes, Block};
 /// ```ignore
 /// div(class="hello", name=format!("{first_name} {last_name}")) {}
 /// ```
+///
+/// ## Without children
+/// An element without children can end with a `;`, or without anything if it is followed by
+/// another node that can't be mistaken for its attributes or children.
+/// ```ignore
+/// input(bind:value=value);
+/// br
+/// span { "text" }
+/// ```
+///
+/// ## With children parameters
+/// A component can pass values to its children, which are bound by a closure-like parameter list
+/// before the children.
+/// ```ignore
+/// Table(rows=rows) |row| { tr { td { (row.name) } } }
+/// ```
+///
+/// ## With selector shorthand
+/// Classes and an id can follow the element name in any order, like a css selector. Since rust
+/// reserves `name#` as a prefix, the id must be separated from what comes before it by a space.
+/// ```ignore
+/// div.card.is-active #main {}
+/// ```
 #[derive(Debug, PartialEq, Hash)]
 pub struct Element {
     pub path: Path,
+    pub classes: Vec<ElementClass>,
+    pub id: Option<ElementId>,
     pub attributes: Option<Attributes>,
-    pub children: Block,
+    pub children_params: Option<ChildrenParams>,
+    /// The children, or `None` for an element without a block
+    pub children: Option<Block>,
+    /// The optional `;` ending an element without children
+    pub semi_token: Option<Token![;]>,
 }
 
 impl Parse for Element {
     fn parse(input: ParseStream) -> Result<Self> {
+        let path = input.parse()?;
+
+        let mut classes = Vec::new();
+        let mut id: Option<ElementId> = None;
+        loop {
+            if input.peek(Token![.]) {
+                classes.push(input.parse()?);
+            } else if input.peek(Token![#]) && (input.peek2(Ident::peek_any) || input.peek2(LitInt))
+            {
+                let span = input.span();
+                if id.replace(input.parse()?).is_some() {
+                    return Err(Error::new(span, "an element can only have one id"));
+                }
+            } else {
+                break;
+            }
+        }
+
+        let attributes = input.peek(Paren).then(|| input.parse()).transpose()?;
+        let children_params: Option<ChildrenParams> =
+            input.peek(Token![|]).then(|| input.parse()).transpose()?;
+        // Children parameters must be followed by the children they are passed to
+        let children = if children_params.is_some() || input.peek(Brace) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let semi_token = if children.is_none() {
+            input.parse()?
+        } else {
+            None
+        };
+
         Ok(Element {
-            path: input.parse()?,
-            attributes: input.peek(Paren).then(|| input.parse()).transpose()?,
-            children: input.parse()?,
+            path,
+            classes,
+            id,
+            attributes,
+            children_params,
+            children,
+            semi_token,
         })
     }
 }
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt};
+
+    use super::*;
+
+    impl ToTokens for SelectorNameSegment {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                SelectorNameSegment::Ident(ident) => ident.to_tokens(tokens),
+                SelectorNameSegment::Int(lit_int) => lit_int.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for SelectorName {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.segments.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for ElementClass {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.dot_token.to_tokens(tokens);
+            self.name.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for ElementId {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.pound_token.to_tokens(tokens);
+            self.name.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for ChildrenParams {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.or1_token.to_tokens(tokens);
+            self.inputs.to_tokens(tokens);
+            self.or2_token.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for Element {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.path.to_tokens(tokens);
+            tokens.append_all(&self.classes);
+            self.id.to_tokens(tokens);
+            self.attributes.to_tokens(tokens);
+            self.children_params.to_tokens(tokens);
+            self.children.to_tokens(tokens);
+            self.semi_token.to_tokens(tokens);
+        }
+    }
+}
@@ -0,0 +1,679 @@
+//! A canonical pretty-printer for rdml syntax.
+//!
+//! Embedded rust expressions are laid out with [`prettyplease`], everything else (elements,
+//! attribute lists, control flow) is laid out by this module according to [`FormatOptions`].
+//!
+//! Match arms are normalized to have a trailing comma exactly when their body is a single text
+//! or expression node.
+//!
+//! # Example
+//!
+//! ```
+//! use rdml::{Nodes, fmt::{FormatOptions, format_nodes}};
+//!
+//! let nodes: Nodes = syn::parse_quote! {
+//!     div(class="container") { span { "Hello" } for item in items { li { (item) } } }
+//! };
+//!
+//! assert_eq!(
+//!     format_nodes(&nodes, &FormatOptions::default()),
+//!     r#"div(class="container") {
+//!     span { "Hello" }
+//!     for item in items {
+//!         li { (item) }
+//!     }
+//! }
+//! "#
+//! );
+//! ```
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Expr, LitStr, MacroDelimiter, Pat, parse_quote};
+
+use crate::{
+    Attribute, AttributeEntry, AttributeName, AttributeValue, Attributes, Block, ClassList,
+    ClassListEntry, Condition, ConditionClause, ConditionalAttributes, Element, ElseNode, ForNode,
+    IfNode, LetNode, MacroNode, MatchNode, MatchNodeArm, Node, NodeType, Nodes, RawContent,
+    SlotNode, StyleObject, StyleProperty, match_node::requires_comma_to_be_match_arm,
+};
+
+/// Layout options for the formatter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// The maximum line width before attribute lists are broken over several lines and single
+    /// children are no longer kept on the same line as their parent.
+    pub max_width: usize,
+
+    /// The number of spaces per indentation level.
+    pub indent_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            indent_width: 4,
+        }
+    }
+}
+
+/// Formats a list of nodes, one top level node per line.
+pub fn format_nodes(nodes: &Nodes, options: &FormatOptions) -> String {
+    let printer = Printer { options };
+    let mut result = String::new();
+    for node in &nodes.nodes {
+        result.push_str(&printer.node(node, 0, 0));
+        result.push('\n');
+    }
+    result
+}
+
+/// Formats a block of nodes (including its braces) as if it was at the top level.
+pub fn format_block(block: &Block, options: &FormatOptions) -> String {
+    Printer { options }.block(&block.nodes, 0, 0, true)
+}
+
+struct Printer<'a> {
+    options: &'a FormatOptions,
+}
+
+impl Printer<'_> {
+    fn pad(&self, indent: usize) -> String {
+        " ".repeat(indent)
+    }
+
+    fn fits(&self, col: usize, text: &str) -> bool {
+        !text.contains('\n') && col + text.len() <= self.options.max_width
+    }
+
+    /// Renders a node. The first line starts at `col`, continuation lines are indented by
+    /// `indent`.
+    fn node(&self, node: &Node, indent: usize, col: usize) -> String {
+        let mut result = String::new();
+        for attr in &node.attrs {
+            result.push_str(&tokens_to_string(attr.to_token_stream()));
+            result.push('\n');
+            result.push_str(&self.pad(indent));
+        }
+        let col = if node.attrs.is_empty() { col } else { indent };
+        result.push_str(&self.node_type(&node.node, indent, col));
+        result
+    }
+
+    fn node_type(&self, node: &NodeType, indent: usize, col: usize) -> String {
+        match node {
+            NodeType::Element(element) => self.element(element, indent, col),
+            NodeType::Text(text_node) => {
+                let lit = match text_node
+                    .rebuilt_format_string(|expr| tokens_to_string(expr.to_token_stream()))
+                {
+                    Some(value) => LitStr::new(&value, text_node.lit.span())
+                        .token()
+                        .to_string(),
+                    None => text_node.lit.token().to_string(),
+                };
+                match text_node.dollar_token {
+                    Some(_) => format!("${lit}"),
+                    None => lit,
+                }
+            }
+            NodeType::Expr(expr_node) => format!("({})", self.expr(&expr_node.expr, indent)),
+            NodeType::If(if_node) => self.if_node(if_node, indent, col),
+            NodeType::For(for_node) => self.for_node(for_node, indent, col),
+            NodeType::Match(match_node) => self.match_node(match_node, indent),
+            NodeType::Let(let_node) => self.let_node(let_node, indent),
+            NodeType::Slot(slot_node) => self.slot_node(slot_node, indent, col),
+            NodeType::Raw(raw_node) => match &raw_node.content {
+                RawContent::Text(lit_str) => format!("raw {}", lit_str.token()),
+                RawContent::Expr(expr_node) => {
+                    format!("raw({})", self.expr(&expr_node.expr, indent))
+                }
+            },
+            NodeType::Macro(macro_node) => self.macro_node(macro_node),
+            NodeType::Block(block) => self.block(&block.nodes, indent, col, false),
+        }
+    }
+
+    /// Renders a block of nodes including its braces. A block with a single simple child is
+    /// kept on one line if `inline` is set and it fits.
+    fn block(&self, nodes: &[Node], indent: usize, col: usize, inline: bool) -> String {
+        if nodes.is_empty() {
+            return "{}".to_string();
+        }
+
+        if let [node] = nodes
+            && inline
+            && node.attrs.is_empty()
+            && matches!(
+                node.node,
+                NodeType::Element(_)
+                    | NodeType::Text(_)
+                    | NodeType::Expr(_)
+                    | NodeType::Raw(_)
+                    | NodeType::Macro(_)
+            )
+        {
+            let inline = format!("{{ {} }}", self.node(node, indent, col + 2));
+            if self.fits(col, &inline) {
+                return inline;
+            }
+        }
+
+        let inner = indent + self.options.indent_width;
+        let mut result = "{\n".to_string();
+        for node in nodes {
+            result.push_str(&self.pad(inner));
+            result.push_str(&self.node(node, inner, inner));
+            result.push('\n');
+        }
+        result.push_str(&self.pad(indent));
+        result.push('}');
+        result
+    }
+
+    fn element(&self, element: &Element, indent: usize, col: usize) -> String {
+        let mut result = tokens_to_string(element.path.to_token_stream());
+        for class in &element.classes {
+            result.push_str(&format!(".{}", class.name));
+        }
+        if let Some(id) = &element.id {
+            result.push_str(&format!(" #{}", id.name));
+        }
+        if let Some(attributes) = &element.attributes {
+            let attributes = self.attributes(attributes, indent, col + result.len());
+            result.push_str(&attributes);
+        }
+        if let Some(params) = &element.children_params {
+            let inputs = params
+                .inputs
+                .iter()
+                .map(|pat| tokens_to_string(pat.to_token_stream()))
+                .collect::<Vec<_>>();
+            result.push_str(&format!(" |{}|", inputs.join(", ")));
+        }
+        match &element.children {
+            Some(children) => {
+                result.push(' ');
+                let col = last_line_col(&result, col);
+                result.push_str(&self.block(&children.nodes, indent, col, true));
+            }
+            None if element.semi_token.is_some() => result.push(';'),
+            None => {}
+        }
+        result
+    }
+
+    fn attributes(&self, attributes: &Attributes, indent: usize, col: usize) -> String {
+        let inline = attributes
+            .attributes
+            .iter()
+            .map(|entry| self.attribute_entry(entry, indent))
+            .collect::<Vec<_>>()
+            .join(", ");
+        // Leave room for the opening brace of the children
+        if self.fits(col, &format!("({inline}) {{")) {
+            return format!("({inline})");
+        }
+
+        let inner = indent + self.options.indent_width;
+        let mut result = "(\n".to_string();
+        for entry in &attributes.attributes {
+            result.push_str(&self.pad(inner));
+            result.push_str(&self.attribute_entry(entry, inner));
+            result.push_str(",\n");
+        }
+        result.push_str(&self.pad(indent));
+        result.push(')');
+        result
+    }
+
+    fn attribute_entry(&self, entry: &AttributeEntry, indent: usize) -> String {
+        let mut result = String::new();
+        for attr in entry.attrs() {
+            result.push_str(&tokens_to_string(attr.to_token_stream()));
+            result.push(' ');
+        }
+        match entry {
+            AttributeEntry::Attribute(attr) => result.push_str(&self.attribute(attr, indent)),
+            AttributeEntry::Spread(spread) => {
+                result.push_str(&format!("..{}", self.expr(&spread.expr, indent)));
+            }
+            AttributeEntry::Conditional(conditional) => {
+                result.push_str(&format!("if {} ", self.expr(&conditional.cond, indent)));
+                let col = last_line_col(&result, indent);
+                result.push_str(&self.conditional_attributes(conditional, indent, col));
+            }
+        }
+        result
+    }
+
+    fn conditional_attributes(
+        &self,
+        conditional: &ConditionalAttributes,
+        indent: usize,
+        col: usize,
+    ) -> String {
+        if conditional.attributes.is_empty() {
+            return "{}".to_string();
+        }
+        let inline = conditional
+            .attributes
+            .iter()
+            .map(|entry| self.attribute_entry(entry, indent))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.fits(col, &format!("{{ {inline} }}")) {
+            return format!("{{ {inline} }}");
+        }
+
+        let inner = indent + self.options.indent_width;
+        let mut result = "{\n".to_string();
+        for entry in &conditional.attributes {
+            result.push_str(&self.pad(inner));
+            result.push_str(&self.attribute_entry(entry, inner));
+            result.push_str(",\n");
+        }
+        result.push_str(&self.pad(indent));
+        result.push('}');
+        result
+    }
+
+    fn attribute(&self, attr: &Attribute, indent: usize) -> String {
+        let mut name = self.attribute_name(&attr.name);
+        if attr.is_optional() {
+            name.push('?');
+        }
+        match &attr.value {
+            Some(AttributeValue::Expr(expr)) => format!("{name}={}", self.expr(expr, indent)),
+            Some(AttributeValue::Markup(block)) => {
+                let col = indent + name.len() + 1;
+                format!("{name}={}", self.block(&block.nodes, indent, col, true))
+            }
+            Some(AttributeValue::ClassList(class_list)) => {
+                let col = indent + name.len() + 1;
+                format!("{name}={}", self.class_list(class_list, indent, col))
+            }
+            Some(AttributeValue::Style(style)) => {
+                let col = indent + name.len() + 1;
+                format!("{name}={}", self.style_object(style, indent, col))
+            }
+            None => name,
+        }
+    }
+
+    fn class_list(&self, class_list: &ClassList, indent: usize, col: usize) -> String {
+        let entry = |entry: &ClassListEntry, indent| {
+            let class = self.expr(&entry.class, indent);
+            match &entry.condition {
+                Some((_, condition)) => format!("{class} if {}", self.expr(condition, indent)),
+                None => class,
+            }
+        };
+
+        let inline = class_list
+            .entries
+            .iter()
+            .map(|it| entry(it, indent))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.fits(col, &format!("[{inline}]")) {
+            return format!("[{inline}]");
+        }
+
+        let inner = indent + self.options.indent_width;
+        let mut result = "[\n".to_string();
+        for it in &class_list.entries {
+            result.push_str(&self.pad(inner));
+            result.push_str(&entry(it, inner));
+            result.push_str(",\n");
+        }
+        result.push_str(&self.pad(indent));
+        result.push(']');
+        result
+    }
+
+    fn style_object(&self, style: &StyleObject, indent: usize, col: usize) -> String {
+        let property = |property: &StyleProperty, indent| {
+            let mut result = format!(
+                "{}: {}",
+                property.name.value(),
+                self.expr(&property.value, indent)
+            );
+            if let Some((_, condition)) = &property.condition {
+                result.push_str(&format!(" if {}", self.expr(condition, indent)));
+            }
+            result
+        };
+
+        if style.properties.is_empty() {
+            return "{}".to_string();
+        }
+        let inline = style
+            .properties
+            .iter()
+            .map(|it| property(it, indent))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.fits(col, &format!("{{ {inline} }}")) {
+            return format!("{{ {inline} }}");
+        }
+
+        let inner = indent + self.options.indent_width;
+        let mut result = "{\n".to_string();
+        for it in &style.properties {
+            result.push_str(&self.pad(inner));
+            result.push_str(&property(it, inner));
+            result.push_str(",\n");
+        }
+        result.push_str(&self.pad(indent));
+        result.push('}');
+        result
+    }
+
+    fn attribute_name(&self, name: &AttributeName) -> String {
+        match name {
+            AttributeName::Single(path) => tokens_to_string(path.to_token_stream()),
+            AttributeName::Quoted(lit_str) => lit_str.token().to_string(),
+            AttributeName::Directive(directive) => format!(
+                "{}:{}",
+                directive.directive,
+                tokens_to_string(directive.path.to_token_stream())
+            ),
+        }
+    }
+
+    fn if_node(&self, if_node: &IfNode, indent: usize, col: usize) -> String {
+        let mut result = format!("if {} ", self.condition(&if_node.cond, indent));
+        let col = last_line_col(&result, col);
+        result.push_str(&self.block(&if_node.then_branch.nodes, indent, col, false));
+        if let Some((_, else_node)) = &if_node.else_branch {
+            result.push_str(" else ");
+            match else_node {
+                ElseNode::If(if_node) => result.push_str(&self.if_node(if_node, indent, indent)),
+                ElseNode::Else(block) => {
+                    result.push_str(&self.block(&block.nodes, indent, indent, false))
+                }
+            }
+        }
+        result
+    }
+
+    fn condition(&self, cond: &Condition, indent: usize) -> String {
+        let clauses = cond.clauses.iter().map(|clause| match clause {
+            ConditionClause::Expr(expr) => self.expr(expr, indent),
+            ConditionClause::Let(let_condition) => format!(
+                "let {} = {}",
+                tokens_to_string(let_condition.pat.to_token_stream()),
+                self.expr(&let_condition.expr, indent)
+            ),
+        });
+        clauses.collect::<Vec<_>>().join(" && ")
+    }
+
+    fn for_node(&self, for_node: &ForNode, indent: usize, col: usize) -> String {
+        let mut result = format!(
+            "for {} in {} ",
+            tokens_to_string(for_node.pat.to_token_stream()),
+            self.expr(&for_node.expr, indent)
+        );
+        if let Some((_, guard)) = &for_node.guard {
+            result.push_str("if ");
+            result.push_str(&self.expr(guard, indent));
+            result.push(' ');
+        }
+        let col = last_line_col(&result, col);
+        result.push_str(&self.block(&for_node.body.nodes, indent, col, false));
+        if let Some((_, block)) = &for_node.separator {
+            result.push_str(" sep ");
+            let col = last_line_col(&result, indent);
+            result.push_str(&self.block(&block.nodes, indent, col, true));
+        }
+        if let Some((_, block)) = &for_node.else_branch {
+            result.push_str(" else ");
+            result.push_str(&self.block(&block.nodes, indent, indent, false));
+        }
+        result
+    }
+
+    fn slot_node(&self, slot_node: &SlotNode, indent: usize, col: usize) -> String {
+        let mut result = format!(
+            "slot {}",
+            tokens_to_string(slot_node.path.to_token_stream())
+        );
+        if let Some(attributes) = &slot_node.attributes {
+            let attributes = self.attributes(attributes, indent, col + result.len());
+            result.push_str(&attributes);
+        }
+        result.push(' ');
+        let col = last_line_col(&result, col);
+        result.push_str(&self.block(&slot_node.children.nodes, indent, col, true));
+        result
+    }
+
+    /// Renders a macro invocation, its tokens are kept on one line since they can be anything
+    fn macro_node(&self, macro_node: &MacroNode) -> String {
+        let mac = &macro_node.mac;
+        let path = tokens_to_string(mac.path.to_token_stream());
+        let tokens = tokens_to_string(mac.tokens.clone());
+        match mac.delimiter {
+            MacroDelimiter::Paren(_) => format!("{path}!({tokens})"),
+            MacroDelimiter::Bracket(_) => format!("{path}![{tokens}]"),
+            MacroDelimiter::Brace(_) if tokens.is_empty() => format!("{path}! {{}}"),
+            MacroDelimiter::Brace(_) => format!("{path}! {{ {tokens} }}"),
+        }
+    }
+
+    fn let_node(&self, let_node: &LetNode, indent: usize) -> String {
+        format!(
+            "let {} = {};",
+            tokens_to_string(let_node.pat.to_token_stream()),
+            self.expr(&let_node.expr, indent)
+        )
+    }
+
+    fn match_node(&self, match_node: &MatchNode, indent: usize) -> String {
+        let mut result = format!("match {} {{", self.expr(&match_node.expr, indent));
+        if match_node.arms.is_empty() {
+            result.push('}');
+            return result;
+        }
+
+        let inner = indent + self.options.indent_width;
+        result.push('\n');
+        for arm in &match_node.arms {
+            result.push_str(&self.pad(inner));
+            result.push_str(&self.match_node_arm(arm, inner));
+            result.push('\n');
+        }
+        result.push_str(&self.pad(indent));
+        result.push('}');
+        result
+    }
+
+    fn match_node_arm(&self, arm: &MatchNodeArm, indent: usize) -> String {
+        let mut result = match &arm.pat {
+            // Drop the optional leading `|`
+            Pat::Or(pat_or) => tokens_to_string(pat_or.cases.to_token_stream()),
+            pat => tokens_to_string(pat.to_token_stream()),
+        };
+        if let Some((_, guard)) = &arm.guard {
+            result.push_str(" if ");
+            result.push_str(&self.expr(guard, indent));
+        }
+        result.push_str(" => ");
+        for attr in &arm.body.attrs {
+            result.push_str(&tokens_to_string(attr.to_token_stream()));
+            result.push(' ');
+        }
+        let col = last_line_col(&result, indent);
+        result.push_str(&self.node_type(&arm.body.node, indent, col));
+        if requires_comma_to_be_match_arm(&arm.body) {
+            result.push(',');
+        }
+        result
+    }
+
+    /// Renders a rust expression with `prettyplease`, indenting continuation lines by `indent`.
+    fn expr(&self, expr: &Expr, indent: usize) -> String {
+        let file: syn::File = parse_quote! { const _: () = #expr; };
+        let source = prettyplease::unparse(&file);
+        let source = source
+            .trim_end()
+            .trim_start_matches("const _: () =")
+            .trim_end_matches(';');
+
+        // Long expressions are moved to their own line by prettyplease, which adds an extra
+        // level of indentation
+        let (source, dedent) = match source.strip_prefix('\n') {
+            Some(source) => (source.trim_start(), 4),
+            None => (source.trim_start(), 0),
+        };
+
+        let mut lines = source.lines();
+        let mut result = lines.next().unwrap_or_default().to_string();
+        for line in lines {
+            let line = line.strip_prefix(&" ".repeat(dedent)).unwrap_or(line);
+            let content = line.trim_start();
+            let levels = (line.len() - content.len()) / 4;
+            result.push('\n');
+            if !content.is_empty() {
+                result.push_str(&self.pad(indent + levels * self.options.indent_width));
+                result.push_str(content);
+            }
+        }
+        result
+    }
+}
+
+fn last_line_col(text: &str, col: usize) -> usize {
+    match text.rfind('\n') {
+        Some(index) => text.len() - index - 1,
+        None => col + text.len(),
+    }
+}
+
+/// Renders a rust expression as it appears in formatted rdml source, at no indentation.
+#[cfg(feature = "serde")]
+pub(crate) fn expr_to_string(expr: &Expr) -> String {
+    Printer {
+        options: &FormatOptions::default(),
+    }
+    .expr(expr, 0)
+}
+
+/// Renders short fragments of rust syntax (paths, patterns and node attributes) on one line.
+pub(crate) fn tokens_to_string(tokens: TokenStream) -> String {
+    let mut result = String::new();
+    write_tokens(&mut result, tokens);
+    result
+}
+
+enum Prev {
+    Start,
+    Ident,
+    Keyword,
+    Literal,
+    Group,
+    /// An operator made of one or more joint punctuation characters
+    Op {
+        op: String,
+        joint: bool,
+        unary: bool,
+    },
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "box", "else", "for", "if", "in", "let", "match", "move", "mut", "ref", "return",
+];
+
+fn write_tokens(result: &mut String, tokens: TokenStream) {
+    let mut prev = Prev::Start;
+
+    for token in tokens {
+        let space = match (&prev, &token) {
+            (Prev::Start, _) | (Prev::Op { joint: true, .. }, _) => false,
+            (_, TokenTree::Punct(punct)) if matches!(punct.as_char(), ',' | ';' | '.' | '?') => {
+                false
+            }
+            (_, TokenTree::Punct(punct)) if punct.as_char() == ':' => {
+                // A leading `::` is the only colon preceded by a space
+                punct.spacing() == Spacing::Joint
+                    && match &prev {
+                        Prev::Keyword | Prev::Literal => true,
+                        Prev::Op { op, .. } => !op.ends_with('>'),
+                        _ => false,
+                    }
+            }
+            (_, TokenTree::Punct(punct)) if matches!(punct.as_char(), '<' | '>') => {
+                !matches!(prev, Prev::Ident | Prev::Op { .. })
+            }
+            (Prev::Ident, TokenTree::Punct(punct)) if punct.as_char() == '!' => false,
+            (Prev::Op { op, unary, .. }, _) => {
+                // e.g. `Component::<T>(...)`
+                let generic_call = op.ends_with('>')
+                    && !matches!(op.as_str(), "=>" | "->" | ">=")
+                    && matches!(&token, TokenTree::Group(group) if group.delimiter() != Delimiter::Brace);
+                let no_space_after =
+                    *unary || matches!(op.as_str(), "." | ".." | "..=" | "::" | "!" | "#" | "<");
+                !no_space_after && !generic_call
+            }
+            (Prev::Ident | Prev::Group, TokenTree::Group(group)) => {
+                group.delimiter() == Delimiter::Brace
+            }
+            _ => true,
+        };
+        if space {
+            result.push(' ');
+        }
+
+        prev = match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace if group.stream().is_empty() => ("{", "}"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                result.push_str(open);
+                write_tokens(result, group.stream());
+                result.push_str(close);
+                Prev::Group
+            }
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                result.push_str(&ident);
+                if KEYWORDS.contains(&ident.as_str()) {
+                    Prev::Keyword
+                } else {
+                    Prev::Ident
+                }
+            }
+            TokenTree::Punct(punct) => {
+                result.push(punct.as_char());
+                let joint = punct.spacing() == Spacing::Joint;
+                match prev {
+                    Prev::Op {
+                        mut op,
+                        joint: true,
+                        unary,
+                    } => {
+                        op.push(punct.as_char());
+                        Prev::Op { op, joint, unary }
+                    }
+                    prev => Prev::Op {
+                        op: punct.as_char().to_string(),
+                        joint,
+                        unary: matches!(punct.as_char(), '&' | '-' | '*')
+                            && matches!(prev, Prev::Start | Prev::Keyword | Prev::Op { .. }),
+                    },
+                }
+            }
+            TokenTree::Literal(literal) => {
+                result.push_str(&literal.to_string());
+                Prev::Literal
+            }
+        };
+    }
+}
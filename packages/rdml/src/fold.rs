@@ -0,0 +1,695 @@
+//! Syntax tree traversal to transform the nodes of an owned rdml syntax tree.
+//!
+//! Each method of the [`Fold`] trait is a hook that can be overridden to customize the
+//! behavior when transforming the corresponding type of node. By default, every method
+//! recursively folds the substructure of the input by invoking the right folder method of
+//! each of its fields.
+//!
+//! Embedded rust syntax (expressions, patterns, paths, ...) is handed to a leaf hook such as
+//! [`Fold::fold_expr`], which returns its input unchanged by default.
+//!
+//! # Example
+//!
+//! ```
+//! use rdml::{Node, NodeType, Nodes, fold::{self, Fold}};
+//!
+//! /// Replaces every `(expr)` node with a `"?"` text node
+//! struct HideExprs;
+//!
+//! impl Fold for HideExprs {
+//!     fn fold_node_type(&mut self, node: NodeType) -> NodeType {
+//!         match node {
+//!             NodeType::Expr(_) => NodeType::Text(syn::parse_quote!("?")),
+//!             node => fold::fold_node_type(self, node),
+//!         }
+//!     }
+//! }
+//!
+//! let nodes: Nodes = syn::parse_quote! {
+//!     div { (secret) }
+//! };
+//!
+//! let expected: Nodes = syn::parse_quote! {
+//!     div { "?" }
+//! };
+//! assert_eq!(HideExprs.fold_nodes(nodes), expected);
+//! ```
+
+use syn::{
+    Expr, Ident, LitStr, Macro, Pat, Path,
+    punctuated::{Pair, Punctuated},
+};
+
+use crate::{
+    Attribute, AttributeEntry, AttributeName, AttributeNameDirective, AttributeValue, Attributes,
+    Block, ChildrenParams, ClassList, ClassListEntry, Condition, ConditionClause,
+    ConditionalAttributes, Element, ElementClass, ElementId, ElseNode, ExprNode, ForNode, IfNode,
+    LetCondition, LetNode, MacroNode, MatchNode, MatchNodeArm, Node, NodeType, Nodes, RawContent,
+    RawNode, SelectorName, SelectorNameSegment, SlotNode, SpreadAttribute, StyleObject,
+    StyleProperty, TextNode, TextSegment,
+};
+
+/// Syntax tree traversal to transform the nodes of an owned rdml syntax tree.
+///
+/// See the [module documentation](self) for details.
+pub trait Fold {
+    fn fold_nodes(&mut self, i: Nodes) -> Nodes {
+        fold_nodes(self, i)
+    }
+
+    fn fold_block(&mut self, i: Block) -> Block {
+        fold_block(self, i)
+    }
+
+    fn fold_node(&mut self, i: Node) -> Node {
+        fold_node(self, i)
+    }
+
+    fn fold_node_type(&mut self, i: NodeType) -> NodeType {
+        fold_node_type(self, i)
+    }
+
+    fn fold_element(&mut self, i: Element) -> Element {
+        fold_element(self, i)
+    }
+
+    fn fold_element_class(&mut self, i: ElementClass) -> ElementClass {
+        fold_element_class(self, i)
+    }
+
+    fn fold_element_id(&mut self, i: ElementId) -> ElementId {
+        fold_element_id(self, i)
+    }
+
+    fn fold_children_params(&mut self, i: ChildrenParams) -> ChildrenParams {
+        fold_children_params(self, i)
+    }
+
+    fn fold_selector_name(&mut self, i: SelectorName) -> SelectorName {
+        fold_selector_name(self, i)
+    }
+
+    fn fold_attributes(&mut self, i: Attributes) -> Attributes {
+        fold_attributes(self, i)
+    }
+
+    fn fold_attribute_entry(&mut self, i: AttributeEntry) -> AttributeEntry {
+        fold_attribute_entry(self, i)
+    }
+
+    fn fold_attribute(&mut self, i: Attribute) -> Attribute {
+        fold_attribute(self, i)
+    }
+
+    fn fold_spread_attribute(&mut self, i: SpreadAttribute) -> SpreadAttribute {
+        fold_spread_attribute(self, i)
+    }
+
+    fn fold_conditional_attributes(&mut self, i: ConditionalAttributes) -> ConditionalAttributes {
+        fold_conditional_attributes(self, i)
+    }
+
+    fn fold_attribute_name(&mut self, i: AttributeName) -> AttributeName {
+        fold_attribute_name(self, i)
+    }
+
+    fn fold_attribute_value(&mut self, i: AttributeValue) -> AttributeValue {
+        fold_attribute_value(self, i)
+    }
+
+    fn fold_class_list(&mut self, i: ClassList) -> ClassList {
+        fold_class_list(self, i)
+    }
+
+    fn fold_class_list_entry(&mut self, i: ClassListEntry) -> ClassListEntry {
+        fold_class_list_entry(self, i)
+    }
+
+    fn fold_style_object(&mut self, i: StyleObject) -> StyleObject {
+        fold_style_object(self, i)
+    }
+
+    fn fold_style_property(&mut self, i: StyleProperty) -> StyleProperty {
+        fold_style_property(self, i)
+    }
+
+    fn fold_attribute_name_directive(
+        &mut self,
+        i: AttributeNameDirective,
+    ) -> AttributeNameDirective {
+        fold_attribute_name_directive(self, i)
+    }
+
+    fn fold_expr_node(&mut self, i: ExprNode) -> ExprNode {
+        fold_expr_node(self, i)
+    }
+
+    fn fold_if_node(&mut self, i: IfNode) -> IfNode {
+        fold_if_node(self, i)
+    }
+
+    fn fold_else_node(&mut self, i: ElseNode) -> ElseNode {
+        fold_else_node(self, i)
+    }
+
+    fn fold_condition(&mut self, i: Condition) -> Condition {
+        fold_condition(self, i)
+    }
+
+    fn fold_let_condition(&mut self, i: LetCondition) -> LetCondition {
+        fold_let_condition(self, i)
+    }
+
+    fn fold_for_node(&mut self, i: ForNode) -> ForNode {
+        fold_for_node(self, i)
+    }
+
+    fn fold_match_node(&mut self, i: MatchNode) -> MatchNode {
+        fold_match_node(self, i)
+    }
+
+    fn fold_match_node_arm(&mut self, i: MatchNodeArm) -> MatchNodeArm {
+        fold_match_node_arm(self, i)
+    }
+
+    fn fold_let_node(&mut self, i: LetNode) -> LetNode {
+        fold_let_node(self, i)
+    }
+
+    fn fold_slot_node(&mut self, i: SlotNode) -> SlotNode {
+        fold_slot_node(self, i)
+    }
+
+    fn fold_raw_node(&mut self, i: RawNode) -> RawNode {
+        fold_raw_node(self, i)
+    }
+
+    fn fold_text_node(&mut self, i: TextNode) -> TextNode {
+        fold_text_node(self, i)
+    }
+
+    fn fold_macro_node(&mut self, i: MacroNode) -> MacroNode {
+        fold_macro_node(self, i)
+    }
+
+    /// Called for each outer attribute on a [`Node`] or an [`AttributeEntry`], e.g. `#[show]`
+    fn fold_node_attr(&mut self, i: syn::Attribute) -> syn::Attribute {
+        i
+    }
+
+    fn fold_expr(&mut self, i: Expr) -> Expr {
+        i
+    }
+
+    fn fold_pat(&mut self, i: Pat) -> Pat {
+        i
+    }
+
+    fn fold_path(&mut self, i: Path) -> Path {
+        i
+    }
+
+    fn fold_ident(&mut self, i: Ident) -> Ident {
+        i
+    }
+
+    fn fold_lit_str(&mut self, i: LitStr) -> LitStr {
+        i
+    }
+}
+
+fn fold_punctuated<T, P>(
+    punctuated: Punctuated<T, P>,
+    mut fold: impl FnMut(T) -> T,
+) -> Punctuated<T, P> {
+    punctuated
+        .into_pairs()
+        .map(|pair| {
+            let (value, punct) = pair.into_tuple();
+            Pair::new(fold(value), punct)
+        })
+        .collect()
+}
+
+pub fn fold_nodes<F>(f: &mut F, node: Nodes) -> Nodes
+where
+    F: Fold + ?Sized,
+{
+    Nodes {
+        nodes: node.nodes.into_iter().map(|it| f.fold_node(it)).collect(),
+    }
+}
+
+pub fn fold_block<F>(f: &mut F, node: Block) -> Block
+where
+    F: Fold + ?Sized,
+{
+    Block {
+        brace_token: node.brace_token,
+        nodes: node.nodes.into_iter().map(|it| f.fold_node(it)).collect(),
+    }
+}
+
+pub fn fold_node<F>(f: &mut F, node: Node) -> Node
+where
+    F: Fold + ?Sized,
+{
+    Node {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_node_attr(it))
+            .collect(),
+        node: f.fold_node_type(node.node),
+    }
+}
+
+pub fn fold_node_type<F>(f: &mut F, node: NodeType) -> NodeType
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        NodeType::Element(element) => NodeType::Element(f.fold_element(element)),
+        NodeType::Text(text_node) => NodeType::Text(f.fold_text_node(text_node)),
+        NodeType::Expr(expr_node) => NodeType::Expr(f.fold_expr_node(expr_node)),
+        NodeType::If(if_node) => NodeType::If(f.fold_if_node(if_node)),
+        NodeType::For(for_node) => NodeType::For(f.fold_for_node(for_node)),
+        NodeType::Match(match_node) => NodeType::Match(f.fold_match_node(match_node)),
+        NodeType::Let(let_node) => NodeType::Let(f.fold_let_node(let_node)),
+        NodeType::Slot(slot_node) => NodeType::Slot(f.fold_slot_node(slot_node)),
+        NodeType::Raw(raw_node) => NodeType::Raw(f.fold_raw_node(raw_node)),
+        NodeType::Macro(macro_node) => NodeType::Macro(f.fold_macro_node(macro_node)),
+        NodeType::Block(block) => NodeType::Block(f.fold_block(block)),
+    }
+}
+
+pub fn fold_element<F>(f: &mut F, node: Element) -> Element
+where
+    F: Fold + ?Sized,
+{
+    Element {
+        path: f.fold_path(node.path),
+        classes: node
+            .classes
+            .into_iter()
+            .map(|it| f.fold_element_class(it))
+            .collect(),
+        id: node.id.map(|it| f.fold_element_id(it)),
+        attributes: node.attributes.map(|it| f.fold_attributes(it)),
+        children_params: node.children_params.map(|it| f.fold_children_params(it)),
+        children: node.children.map(|it| f.fold_block(it)),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_element_class<F>(f: &mut F, node: ElementClass) -> ElementClass
+where
+    F: Fold + ?Sized,
+{
+    ElementClass {
+        dot_token: node.dot_token,
+        name: f.fold_selector_name(node.name),
+    }
+}
+
+pub fn fold_children_params<F>(f: &mut F, node: ChildrenParams) -> ChildrenParams
+where
+    F: Fold + ?Sized,
+{
+    ChildrenParams {
+        or1_token: node.or1_token,
+        inputs: fold_punctuated(node.inputs, |it| f.fold_pat(it)),
+        or2_token: node.or2_token,
+    }
+}
+
+pub fn fold_element_id<F>(f: &mut F, node: ElementId) -> ElementId
+where
+    F: Fold + ?Sized,
+{
+    ElementId {
+        pound_token: node.pound_token,
+        name: f.fold_selector_name(node.name),
+    }
+}
+
+pub fn fold_selector_name<F>(f: &mut F, node: SelectorName) -> SelectorName
+where
+    F: Fold + ?Sized,
+{
+    SelectorName {
+        segments: fold_punctuated(node.segments, |it| match it {
+            SelectorNameSegment::Ident(ident) => SelectorNameSegment::Ident(f.fold_ident(ident)),
+            segment => segment,
+        }),
+    }
+}
+
+pub fn fold_attributes<F>(f: &mut F, node: Attributes) -> Attributes
+where
+    F: Fold + ?Sized,
+{
+    Attributes {
+        paren_token: node.paren_token,
+        attributes: fold_punctuated(node.attributes, |it| f.fold_attribute_entry(it)),
+    }
+}
+
+pub fn fold_attribute_entry<F>(f: &mut F, node: AttributeEntry) -> AttributeEntry
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        AttributeEntry::Attribute(attribute) => {
+            AttributeEntry::Attribute(f.fold_attribute(attribute))
+        }
+        AttributeEntry::Spread(spread) => AttributeEntry::Spread(f.fold_spread_attribute(spread)),
+        AttributeEntry::Conditional(conditional) => {
+            AttributeEntry::Conditional(f.fold_conditional_attributes(conditional))
+        }
+    }
+}
+
+pub fn fold_attribute<F>(f: &mut F, node: Attribute) -> Attribute
+where
+    F: Fold + ?Sized,
+{
+    Attribute {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_node_attr(it))
+            .collect(),
+        name: f.fold_attribute_name(node.name),
+        question_token: node.question_token,
+        eq_token: node.eq_token,
+        value: node.value.map(|it| f.fold_attribute_value(it)),
+    }
+}
+
+pub fn fold_attribute_value<F>(f: &mut F, node: AttributeValue) -> AttributeValue
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        AttributeValue::Expr(expr) => AttributeValue::Expr(f.fold_expr(expr)),
+        AttributeValue::Markup(block) => AttributeValue::Markup(f.fold_block(block)),
+        AttributeValue::ClassList(class_list) => {
+            AttributeValue::ClassList(f.fold_class_list(class_list))
+        }
+        AttributeValue::Style(style) => AttributeValue::Style(f.fold_style_object(style)),
+    }
+}
+
+pub fn fold_class_list<F>(f: &mut F, node: ClassList) -> ClassList
+where
+    F: Fold + ?Sized,
+{
+    ClassList {
+        bracket_token: node.bracket_token,
+        entries: fold_punctuated(node.entries, |it| f.fold_class_list_entry(it)),
+    }
+}
+
+pub fn fold_class_list_entry<F>(f: &mut F, node: ClassListEntry) -> ClassListEntry
+where
+    F: Fold + ?Sized,
+{
+    ClassListEntry {
+        class: f.fold_expr(node.class),
+        condition: node
+            .condition
+            .map(|(if_token, condition)| (if_token, f.fold_expr(condition))),
+    }
+}
+
+pub fn fold_style_object<F>(f: &mut F, node: StyleObject) -> StyleObject
+where
+    F: Fold + ?Sized,
+{
+    StyleObject {
+        brace_token: node.brace_token,
+        properties: fold_punctuated(node.properties, |it| f.fold_style_property(it)),
+    }
+}
+
+pub fn fold_style_property<F>(f: &mut F, node: StyleProperty) -> StyleProperty
+where
+    F: Fold + ?Sized,
+{
+    StyleProperty {
+        name: node.name,
+        colon_token: node.colon_token,
+        value: f.fold_expr(node.value),
+        condition: node
+            .condition
+            .map(|(if_token, condition)| (if_token, f.fold_expr(condition))),
+    }
+}
+
+pub fn fold_spread_attribute<F>(f: &mut F, node: SpreadAttribute) -> SpreadAttribute
+where
+    F: Fold + ?Sized,
+{
+    SpreadAttribute {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_node_attr(it))
+            .collect(),
+        dot2_token: node.dot2_token,
+        expr: f.fold_expr(node.expr),
+    }
+}
+
+pub fn fold_conditional_attributes<F>(
+    f: &mut F,
+    node: ConditionalAttributes,
+) -> ConditionalAttributes
+where
+    F: Fold + ?Sized,
+{
+    ConditionalAttributes {
+        attrs: node
+            .attrs
+            .into_iter()
+            .map(|it| f.fold_node_attr(it))
+            .collect(),
+        if_token: node.if_token,
+        cond: f.fold_expr(node.cond),
+        brace_token: node.brace_token,
+        attributes: fold_punctuated(node.attributes, |it| f.fold_attribute_entry(it)),
+    }
+}
+
+pub fn fold_attribute_name<F>(f: &mut F, node: AttributeName) -> AttributeName
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        AttributeName::Single(path) => AttributeName::Single(f.fold_path(path)),
+        AttributeName::Quoted(lit_str) => AttributeName::Quoted(f.fold_lit_str(lit_str)),
+        AttributeName::Directive(directive) => {
+            AttributeName::Directive(f.fold_attribute_name_directive(directive))
+        }
+    }
+}
+
+pub fn fold_attribute_name_directive<F>(
+    f: &mut F,
+    node: AttributeNameDirective,
+) -> AttributeNameDirective
+where
+    F: Fold + ?Sized,
+{
+    AttributeNameDirective {
+        directive: f.fold_ident(node.directive),
+        colon_token: node.colon_token,
+        path: f.fold_path(node.path),
+    }
+}
+
+pub fn fold_expr_node<F>(f: &mut F, node: ExprNode) -> ExprNode
+where
+    F: Fold + ?Sized,
+{
+    ExprNode {
+        paren_token: node.paren_token,
+        expr: f.fold_expr(node.expr),
+    }
+}
+
+pub fn fold_if_node<F>(f: &mut F, node: IfNode) -> IfNode
+where
+    F: Fold + ?Sized,
+{
+    IfNode {
+        if_token: node.if_token,
+        cond: f.fold_condition(node.cond),
+        then_branch: f.fold_block(node.then_branch),
+        else_branch: node
+            .else_branch
+            .map(|(else_token, else_node)| (else_token, f.fold_else_node(else_node))),
+    }
+}
+
+pub fn fold_else_node<F>(f: &mut F, node: ElseNode) -> ElseNode
+where
+    F: Fold + ?Sized,
+{
+    match node {
+        ElseNode::If(if_node) => ElseNode::If(Box::new(f.fold_if_node(*if_node))),
+        ElseNode::Else(block) => ElseNode::Else(f.fold_block(block)),
+    }
+}
+
+pub fn fold_condition<F>(f: &mut F, node: Condition) -> Condition
+where
+    F: Fold + ?Sized,
+{
+    Condition {
+        clauses: fold_punctuated(node.clauses, |it| match it {
+            ConditionClause::Expr(expr) => ConditionClause::Expr(f.fold_expr(expr)),
+            ConditionClause::Let(let_condition) => {
+                ConditionClause::Let(f.fold_let_condition(let_condition))
+            }
+        }),
+    }
+}
+
+pub fn fold_let_condition<F>(f: &mut F, node: LetCondition) -> LetCondition
+where
+    F: Fold + ?Sized,
+{
+    LetCondition {
+        let_token: node.let_token,
+        pat: Box::new(f.fold_pat(*node.pat)),
+        eq_token: node.eq_token,
+        expr: f.fold_expr(node.expr),
+    }
+}
+
+pub fn fold_for_node<F>(f: &mut F, node: ForNode) -> ForNode
+where
+    F: Fold + ?Sized,
+{
+    ForNode {
+        for_token: node.for_token,
+        pat: f.fold_pat(node.pat),
+        in_token: node.in_token,
+        expr: f.fold_expr(node.expr),
+        guard: node
+            .guard
+            .map(|(if_token, guard)| (if_token, Box::new(f.fold_expr(*guard)))),
+        body: f.fold_block(node.body),
+        separator: node
+            .separator
+            .map(|(sep_token, block)| (sep_token, f.fold_block(block))),
+        else_branch: node
+            .else_branch
+            .map(|(else_token, block)| (else_token, f.fold_block(block))),
+    }
+}
+
+pub fn fold_match_node<F>(f: &mut F, node: MatchNode) -> MatchNode
+where
+    F: Fold + ?Sized,
+{
+    MatchNode {
+        match_token: node.match_token,
+        expr: f.fold_expr(node.expr),
+        brace_token: node.brace_token,
+        arms: node
+            .arms
+            .into_iter()
+            .map(|it| f.fold_match_node_arm(it))
+            .collect(),
+    }
+}
+
+pub fn fold_match_node_arm<F>(f: &mut F, node: MatchNodeArm) -> MatchNodeArm
+where
+    F: Fold + ?Sized,
+{
+    MatchNodeArm {
+        pat: f.fold_pat(node.pat),
+        guard: node
+            .guard
+            .map(|(if_token, guard)| (if_token, f.fold_expr(guard))),
+        fat_arrow_token: node.fat_arrow_token,
+        body: f.fold_node(node.body),
+        comma: node.comma,
+    }
+}
+
+pub fn fold_let_node<F>(f: &mut F, node: LetNode) -> LetNode
+where
+    F: Fold + ?Sized,
+{
+    LetNode {
+        let_token: node.let_token,
+        pat: f.fold_pat(node.pat),
+        eq_token: node.eq_token,
+        expr: f.fold_expr(node.expr),
+        semi_token: node.semi_token,
+    }
+}
+
+pub fn fold_slot_node<F>(f: &mut F, node: SlotNode) -> SlotNode
+where
+    F: Fold + ?Sized,
+{
+    SlotNode {
+        slot_token: node.slot_token,
+        path: f.fold_path(node.path),
+        attributes: node.attributes.map(|it| f.fold_attributes(it)),
+        children: f.fold_block(node.children),
+    }
+}
+
+pub fn fold_raw_node<F>(f: &mut F, node: RawNode) -> RawNode
+where
+    F: Fold + ?Sized,
+{
+    RawNode {
+        raw_token: node.raw_token,
+        content: match node.content {
+            RawContent::Text(lit_str) => RawContent::Text(f.fold_lit_str(lit_str)),
+            RawContent::Expr(expr_node) => RawContent::Expr(Box::new(f.fold_expr_node(*expr_node))),
+        },
+    }
+}
+
+pub fn fold_text_node<F>(f: &mut F, node: TextNode) -> TextNode
+where
+    F: Fold + ?Sized,
+{
+    TextNode {
+        dollar_token: node.dollar_token,
+        lit: f.fold_lit_str(node.lit),
+        segments: node.segments.map(|segments| {
+            segments
+                .into_iter()
+                .map(|it| match it {
+                    TextSegment::Expr { expr, spec } => TextSegment::Expr {
+                        expr: Box::new(f.fold_expr(*expr)),
+                        spec,
+                    },
+                    segment => segment,
+                })
+                .collect()
+        }),
+    }
+}
+
+pub fn fold_macro_node<F>(f: &mut F, node: MacroNode) -> MacroNode
+where
+    F: Fold + ?Sized,
+{
+    MacroNode {
+        mac: Macro {
+            path: f.fold_path(node.mac.path),
+            ..node.mac
+        },
+    }
+}
@@ -1,16 +1,26 @@
 use syn::{
     Expr, Pat, Result, Token,
     parse::{Parse, ParseStream},
+    token::Brace,
 };
 
-use crate::Block;
+use crate::{Block, kw};
 
 /// A for node
 ///
+/// Items can be skipped with an `if` guard, and the optional `sep` block is rendered between
+/// each pair of rendered items. The optional `else` block is rendered instead if no items are
+/// rendered. The guard matches the pattern against a reference to the item, like the predicate of
+/// [`Iterator::filter`], while the body binds the item by value.
+///
 /// # Example
 /// ```ignore
-/// for pattern in expr {
+/// for pattern in expr if guard {
 ///     div {}
+/// } sep {
+///     ", "
+/// } else {
+///     "No items"
 /// }
 /// ```
 #[derive(Debug, PartialEq, Hash)]
@@ -19,7 +29,10 @@ pub struct ForNode {
     pub pat: Pat,
     pub in_token: Token![in],
     pub expr: Expr,
+    pub guard: Option<(Token![if], Box<Expr>)>,
     pub body: Block,
+    pub separator: Option<(kw::sep, Block)>,
+    pub else_branch: Option<(Token![else], Block)>,
 }
 
 impl Parse for ForNode {
@@ -29,7 +42,52 @@ impl Parse for ForNode {
             pat: input.call(Pat::parse_single)?,
             in_token: input.parse()?,
             expr: input.call(Expr::parse_without_eager_brace)?,
+            guard: input
+                .peek(Token![if])
+                .then(|| {
+                    let if_token = input.parse()?;
+                    let guard = input.call(Expr::parse_without_eager_brace)?;
+                    Result::Ok((if_token, Box::new(guard)))
+                })
+                .transpose()?,
             body: input.parse()?,
+            separator: (input.peek(kw::sep) && input.peek2(Brace))
+                .then(|| Result::Ok((input.parse()?, input.parse()?)))
+                .transpose()?,
+            else_branch: input
+                .peek(Token![else])
+                .then(|| Result::Ok((input.parse()?, input.parse()?)))
+                .transpose()?,
         })
     }
 }
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for ForNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.for_token.to_tokens(tokens);
+            self.pat.to_tokens(tokens);
+            self.in_token.to_tokens(tokens);
+            self.expr.to_tokens(tokens);
+            if let Some((if_token, guard)) = &self.guard {
+                if_token.to_tokens(tokens);
+                guard.to_tokens(tokens);
+            }
+            self.body.to_tokens(tokens);
+            if let Some((sep_token, block)) = &self.separator {
+                sep_token.to_tokens(tokens);
+                block.to_tokens(tokens);
+            }
+            if let Some((else_token, block)) = &self.else_branch {
+                else_token.to_tokens(tokens);
+                block.to_tokens(tokens);
+            }
+        }
+    }
+}
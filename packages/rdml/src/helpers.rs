@@ -1,8 +1,75 @@
+use std::cell::{Cell, RefCell};
+
+use proc_macro2::{Delimiter, TokenTree};
 use syn::{
-    Result,
-    parse::{Parse, ParseBuffer},
+    Error, Lit, Pat, PatType, Result, Token,
+    parse::{Parse, ParseBuffer, ParseStream, discouraged::Speculative},
 };
 
+thread_local! {
+    /// The errors collected by the innermost [`with_recovery`] call, if any.
+    static RECOVERED_ERRORS: RefCell<Option<Vec<Error>>> = const { RefCell::new(None) };
+
+    /// Whether text literals are parsed as format strings, set inside of `#[fmt]` nodes.
+    static FORMAT_TEXT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the previous recovery state when dropped.
+struct RecoveryGuard(Option<Vec<Error>>);
+
+impl Drop for RecoveryGuard {
+    fn drop(&mut self) {
+        RECOVERED_ERRORS.with(|errors| *errors.borrow_mut() = self.0.take());
+    }
+}
+
+/// Runs `f` with error recovery enabled for every [`ParseHelpers::parse_all`] call inside of
+/// it, returning the result of `f` and the errors that were recovered from.
+pub(crate) fn with_recovery<T>(f: impl FnOnce() -> T) -> (T, Vec<Error>) {
+    let previous = RECOVERED_ERRORS.with(|errors| errors.borrow_mut().replace(Vec::new()));
+    let guard = RecoveryGuard(previous);
+    let result = f();
+    let errors = RECOVERED_ERRORS
+        .with(|errors| errors.borrow_mut().take())
+        .unwrap_or_default();
+    drop(guard);
+    (result, errors)
+}
+
+/// Restores the previous format text state when dropped.
+struct FormatTextGuard(bool);
+
+impl Drop for FormatTextGuard {
+    fn drop(&mut self) {
+        FORMAT_TEXT.with(|format_text| format_text.set(self.0));
+    }
+}
+
+/// Runs `f` with every text literal inside of it parsed as a format string.
+pub(crate) fn with_format_text<T>(f: impl FnOnce() -> T) -> T {
+    let _guard = FormatTextGuard(FORMAT_TEXT.with(|format_text| format_text.replace(true)));
+    f()
+}
+
+pub(crate) fn is_format_text() -> bool {
+    FORMAT_TEXT.with(Cell::get)
+}
+
+/// Records `error` if recovery is enabled, otherwise gives it back.
+fn recover(error: Error) -> Result<()> {
+    RECOVERED_ERRORS.with(|errors| match &mut *errors.borrow_mut() {
+        Some(errors) => {
+            errors.push(error);
+            Ok(())
+        }
+        None => Err(error),
+    })
+}
+
+fn is_recovering() -> bool {
+    RECOVERED_ERRORS.with(|errors| errors.borrow().is_some())
+}
+
 pub(crate) trait ParseHelpers {
     fn parse_all<T: Parse>(&self) -> Result<Vec<T>>;
 }
@@ -11,8 +78,84 @@ impl<'a> ParseHelpers for ParseBuffer<'a> {
     fn parse_all<T: Parse>(&self) -> Result<Vec<T>> {
         let mut result = Vec::new();
         while !self.is_empty() {
-            result.push(self.parse()?)
+            if !is_recovering() {
+                result.push(self.parse()?);
+                continue;
+            }
+
+            // Parse on a fork so that a partially parsed item doesn't leave any unexpected
+            // tokens behind in `self`
+            let fork = self.fork();
+            match fork.parse() {
+                Ok(value) => {
+                    self.advance_to(&fork);
+                    result.push(value);
+                }
+                Err(error) => {
+                    recover(error)?;
+                    skip_to_next_sibling(self, &fork)?;
+                }
+            }
         }
         Ok(result)
     }
 }
+
+/// Parses a single pattern with an optional type annotation, like the pattern of a `let`
+/// statement or a closure parameter. An annotated pattern is returned as a [`Pat::Type`].
+pub(crate) fn parse_pat_with_type(input: ParseStream) -> Result<Pat> {
+    let pat = input.call(Pat::parse_single)?;
+    if input.peek(Token![:]) {
+        Ok(Pat::Type(PatType {
+            attrs: Vec::new(),
+            pat: Box::new(pat),
+            colon_token: input.parse()?,
+            ty: input.parse()?,
+        }))
+    } else {
+        Ok(pat)
+    }
+}
+
+/// Skips past the point where `failed` stopped parsing, up to and including the next brace
+/// delimited group, `;` or `,`, or up to the next token that unambiguously starts a node.
+fn skip_to_next_sibling(input: &ParseBuffer, failed: &ParseBuffer) -> Result<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        while rest != failed.cursor() {
+            match rest.token_tree() {
+                Some((_, next)) => rest = next,
+                None => break,
+            }
+        }
+
+        let mut skipped_any = rest != *cursor;
+        while let Some((token, next)) = rest.token_tree() {
+            let starts_node = match &token {
+                TokenTree::Punct(punct) => punct.as_char() == '#',
+                TokenTree::Ident(ident) => {
+                    ident == "if" || ident == "for" || ident == "match" || ident == "let"
+                }
+                TokenTree::Literal(literal) => matches!(Lit::new(literal.clone()), Lit::Str(_)),
+                TokenTree::Group(_) => false,
+            };
+            if starts_node && skipped_any {
+                break;
+            }
+
+            rest = next;
+            skipped_any = true;
+
+            let ends_node = match &token {
+                TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+                TokenTree::Punct(punct) => punct.as_char() == ';' || punct.as_char() == ',',
+                _ => false,
+            };
+            if ends_node {
+                break;
+            }
+        }
+
+        Ok(((), rest))
+    })
+}
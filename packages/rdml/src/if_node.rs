@@ -1,17 +1,120 @@
 use syn::{
-    Expr, Result, Token,
+    BinOp, Expr, ExprBinary, ExprLet, Pat, Result, Token,
     parse::{Parse, ParseStream},
+    punctuated::Punctuated,
 };
 
 use crate::Block;
 
+/// A `let` clause of a [`Condition`]: `let Some(user) = user.get()`
+#[derive(Debug, PartialEq, Hash)]
+pub struct LetCondition {
+    pub let_token: Token![let],
+    pub pat: Box<Pat>,
+    pub eq_token: Token![=],
+    /// The scrutinee matched against the pattern
+    pub expr: Expr,
+}
+
+/// A clause of a [`Condition`]
+#[derive(Debug, PartialEq, Hash)]
+pub enum ConditionClause {
+    /// A boolean expression: `count > 0`
+    Expr(Expr),
+
+    /// A pattern match: `let Some(user) = user.get()`
+    Let(LetCondition),
+}
+
+/// The condition of an [`IfNode`], a boolean expression or a `&&` separated let-chain
+///
+/// A condition without any `let` is kept as a single [`ConditionClause::Expr`], even if it
+/// contains `&&`. Otherwise every operand of the chain is a separate clause.
+#[derive(Debug, PartialEq, Hash)]
+pub struct Condition {
+    pub clauses: Punctuated<ConditionClause, Token![&&]>,
+}
+
+impl Condition {
+    /// Whether any clause of the condition is a `let`
+    pub fn has_let(&self) -> bool {
+        self.clauses
+            .iter()
+            .any(|clause| matches!(clause, ConditionClause::Let(_)))
+    }
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let expr = input.call(Expr::parse_without_eager_brace)?;
+        let mut clauses = Punctuated::new();
+        if chain_has_let(&expr) {
+            split_let_chain(expr, &mut clauses);
+        } else {
+            clauses.push_value(ConditionClause::Expr(expr));
+        }
+        Ok(Self { clauses })
+    }
+}
+
+/// Returns the operands of a top level `&&`, which is left associative: `(a && b) && c`
+fn and_operands(expr: &Expr) -> Option<(&Expr, &Expr)> {
+    match expr {
+        Expr::Binary(ExprBinary {
+            attrs,
+            left,
+            op: BinOp::And(_),
+            right,
+        }) if attrs.is_empty() => Some((left, right)),
+        _ => None,
+    }
+}
+
+fn chain_has_let(expr: &Expr) -> bool {
+    match and_operands(expr) {
+        Some((left, right)) => chain_has_let(left) || matches!(right, Expr::Let(_)),
+        None => matches!(expr, Expr::Let(_)),
+    }
+}
+
+fn split_let_chain(expr: Expr, clauses: &mut Punctuated<ConditionClause, Token![&&]>) {
+    match expr {
+        Expr::Binary(ExprBinary {
+            attrs,
+            left,
+            op: BinOp::And(and_token),
+            right,
+        }) if attrs.is_empty() => {
+            split_let_chain(*left, clauses);
+            clauses.push_punct(and_token);
+            split_let_chain(*right, clauses);
+        }
+        Expr::Let(ExprLet {
+            let_token,
+            pat,
+            eq_token,
+            expr,
+            ..
+        }) => clauses.push_value(ConditionClause::Let(LetCondition {
+            let_token,
+            pat,
+            eq_token,
+            expr: *expr,
+        })),
+        expr => clauses.push_value(ConditionClause::Expr(expr)),
+    }
+}
+
 /// An if template literal
 ///
+/// The condition can be an `if let` pattern match or a let-chain, whose bindings are available
+/// in the then branch.
+///
 /// # Example
 /// ```ignore
 /// if condition {
 ///     div {}
-/// } else if condition {
+/// } else if let Some(user) = user.get() && user.is_admin {
 ///     div {}
 /// } else {
 ///     div {}
@@ -20,7 +123,7 @@ use crate::Block;
 #[derive(Debug, PartialEq, Hash)]
 pub struct IfNode {
     pub if_token: Token![if],
-    pub cond: Expr,
+    pub cond: Condition,
     pub then_branch: Block,
     pub else_branch: Option<(Token![else], ElseNode)>,
 }
@@ -29,7 +132,7 @@ impl Parse for IfNode {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
             if_token: input.parse()?,
-            cond: input.call(Expr::parse_without_eager_brace)?,
+            cond: input.parse()?,
             then_branch: input.parse()?,
             else_branch: input
                 .peek(Token![else])
@@ -55,3 +158,56 @@ impl Parse for ElseNode {
         }
     }
 }
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for LetCondition {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.let_token.to_tokens(tokens);
+            self.pat.to_tokens(tokens);
+            self.eq_token.to_tokens(tokens);
+            self.expr.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for ConditionClause {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                ConditionClause::Expr(expr) => expr.to_tokens(tokens),
+                ConditionClause::Let(let_condition) => let_condition.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for Condition {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.clauses.to_tokens(tokens);
+        }
+    }
+
+    impl ToTokens for IfNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.if_token.to_tokens(tokens);
+            self.cond.to_tokens(tokens);
+            self.then_branch.to_tokens(tokens);
+            if let Some((else_token, else_node)) = &self.else_branch {
+                else_token.to_tokens(tokens);
+                else_node.to_tokens(tokens);
+            }
+        }
+    }
+
+    impl ToTokens for ElseNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                ElseNode::If(if_node) => if_node.to_tokens(tokens),
+                ElseNode::Else(block) => block.to_tokens(tokens),
+            }
+        }
+    }
+}
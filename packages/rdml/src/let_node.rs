@@ -0,0 +1,54 @@
+use syn::{
+    Expr, Pat, Result, Token,
+    parse::{Parse, ParseStream},
+};
+
+use crate::helpers::parse_pat_with_type;
+
+/// A let node, whose bindings are in scope for the nodes after it in the enclosing block
+///
+/// # Example
+/// ```ignore
+/// let (first, last) = name.split_once(' ').unwrap();
+/// let count: usize = items.len();
+/// span { (first) }
+/// ```
+#[derive(Debug, PartialEq, Hash)]
+pub struct LetNode {
+    pub let_token: Token![let],
+    /// The pattern, a [`Pat::Type`] if the binding has a type annotation
+    pub pat: Pat,
+    pub eq_token: Token![=],
+    pub expr: Expr,
+    pub semi_token: Token![;],
+}
+
+impl Parse for LetNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            let_token: input.parse()?,
+            pat: input.call(parse_pat_with_type)?,
+            eq_token: input.parse()?,
+            expr: input.parse()?,
+            semi_token: input.parse()?,
+        })
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for LetNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.let_token.to_tokens(tokens);
+            self.pat.to_tokens(tokens);
+            self.eq_token.to_tokens(tokens);
+            self.expr.to_tokens(tokens);
+            self.semi_token.to_tokens(tokens);
+        }
+    }
+}
@@ -23,6 +23,21 @@
 //!     .into()
 //! }
 //! ```
+//!
+//! # Features
+//!
+//!  - `printing` (default): [`quote::ToTokens`] implementations for every syntax tree type, so
+//!    that a parsed template can be emitted again as rdml source with its original spans
+//!  - `source-map`: [`parse_str`] and [`parse_file`] to parse templates outside of a procedural
+//!    macro, with a [`SourceMap`] to resolve spans to byte offsets and line/column positions
+//!  - `fmt`: [`fmt::format_nodes`], a canonical pretty-printer for rdml source
+//!  - `sanitize`: [`sanitize::sanitize_html`] and [`sanitize::check_html`], an allowlist based
+//!    html sanitizer for raw html nodes, re-exported from the `rdml_sanitize` crate
+//!  - `serde`: [`serde::Serialize`] implementations for every syntax tree type, producing the
+//!    versioned shape documented in the [`serialize`] module
+//!  - `visit`: [`visit::Visit`] trait for walking a shared borrow of a syntax tree
+//!  - `visit-mut`: [`visit_mut::VisitMut`] trait for mutating a syntax tree in place
+//!  - `fold`: [`fold::Fold`] trait for transforming an owned syntax tree
 
 mod helpers;
 
@@ -31,16 +46,52 @@ mod block;
 mod element;
 mod for_node;
 mod if_node;
+mod let_node;
+mod macro_node;
 mod match_node;
 mod node;
+mod raw_node;
+mod recovered;
+mod slot_node;
+#[cfg(feature = "source-map")]
+mod source_map;
+mod text_node;
+
+#[cfg(feature = "fmt")]
+pub mod fmt;
+#[cfg(feature = "fold")]
+pub mod fold;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "visit")]
+pub mod visit;
+#[cfg(feature = "visit-mut")]
+pub mod visit_mut;
+
+/// Custom keywords used by the syntax tree
+pub mod kw {
+    syn::custom_keyword!(raw);
+    syn::custom_keyword!(sep);
+    syn::custom_keyword!(slot);
+}
 
 pub use attribute::*;
 pub use block::*;
 pub use element::*;
 pub use for_node::*;
 pub use if_node::*;
+pub use let_node::*;
+pub use macro_node::*;
 pub use match_node::*;
 pub use node::*;
+pub use raw_node::*;
+#[cfg(feature = "sanitize")]
+pub use rdml_sanitize as sanitize;
+pub use recovered::*;
+pub use slot_node::*;
+#[cfg(feature = "source-map")]
+pub use source_map::*;
+pub use text_node::*;
 
 #[cfg(test)]
 mod tests {
@@ -50,15 +101,72 @@ mod tests {
         ($($input:tt)*) => {{
             let result: Nodes = syn::parse_quote! {$($input)*};
             insta::assert_debug_snapshot!(result);
+            #[cfg(feature = "printing")]
+            assert_round_trip(&result);
         }};
     }
 
+    #[cfg(feature = "printing")]
+    fn assert_round_trip(nodes: &Nodes) {
+        let tokens = quote::ToTokens::to_token_stream(nodes);
+        let reparsed: Nodes = syn::parse2(tokens).unwrap();
+        assert_eq!(&reparsed, nodes);
+    }
+
     #[test]
     fn test_parse_empty() {
         let result: Nodes = syn::parse_quote! {};
         assert_eq!(result, Nodes { nodes: vec![] })
     }
 
+    #[test]
+    fn test_recover_errors() {
+        let recovered: Recovered<Nodes> = syn::parse_quote! {
+            div(class=) { "skipped" }
+            span {}
+            p {
+                if {}
+                "kept"
+                a = b
+                #[attr]
+                "kept"
+            }
+            match x {
+                Some(_) => (),
+                None => "kept",
+            }
+        };
+
+        let errors = recovered
+            .errors
+            .iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                "unexpected end of input, expected an expression",
+                "expected curly braces",
+                "expected identifier",
+                "unexpected end of input, expected an expression",
+            ]
+        );
+
+        let expected: Nodes = syn::parse_quote! {
+            span {}
+            p {
+                "kept"
+                a
+                #[attr]
+                "kept"
+            }
+            match x {
+                None => "kept",
+            }
+        };
+        assert_eq!(recovered.value, expected);
+    }
+
     #[test]
     fn test_parse_single_node() {
         snapshot_test! {
@@ -68,6 +176,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_selector_shorthand() {
+        // `parse_quote!` would interpolate `#main`
+        let source = r#"
+            div.card {}
+            div #main.is-active.mt-4(class="extra") {}
+            ::path::Component.r#type #r#type {}
+        "#;
+        let result: Nodes = syn::parse_str(source).unwrap();
+        insta::assert_debug_snapshot!(result);
+        #[cfg(feature = "printing")]
+        assert_round_trip(&result);
+        #[cfg(feature = "fmt")]
+        assert_eq!(
+            crate::fmt::format_nodes(&result, &Default::default()),
+            "div.card {}\ndiv.is-active.mt-4 #main(class=\"extra\") {}\n::path::Component.type #type {}\n"
+        );
+
+        let error = syn::parse_str::<Nodes>("div #a #b {}").unwrap_err();
+        assert_eq!(error.to_string(), "an element can only have one id");
+    }
+
     #[test]
     fn test_match_node() {
         snapshot_test! {
@@ -92,6 +222,44 @@ mod tests {
                 ::rdml::attribute::Attribute="leading non-directive path",
                 rdml::attribute::Attribute="non-directive path",
                 directive:rdml::attribute::Attribute="directive path",
+                ..attrs,
+                ..(class="a", id="b"),
+                punned,
+                "boolean",
+                prop:boolean,
+                markup={ span { "markup" } },
+                block=({ let a = 1; a }),
+                optional?=maybe,
+                "aria-describedby"?=describedby,
+            ) {}
+        }
+    }
+
+    #[test]
+    fn test_parse_class_list() {
+        snapshot_test! {
+            button(class=["btn", "btn-primary" if primary, (extra),], items=[1, 2]) {}
+            div(class=[]) {}
+        }
+    }
+
+    #[test]
+    fn test_parse_style_object() {
+        snapshot_test! {
+            div(style={ color: "red", margin-top: (px(gap)), display: "none" if hidden, r#type: x }) {}
+            div(style={}, other={ "markup" }) {}
+        }
+    }
+
+    #[test]
+    fn test_parse_conditional_attributes() {
+        snapshot_test! {
+            nav(
+                #[cfg(debug_assertions)] "data-debug"="true",
+                if collapsible { "aria-expanded"=open, role="menu", if nested { "aria-level"=2 } },
+                #[cfg(feature = "spread")] ..attrs,
+                #[cfg(test)] if empty {},
+                title,
             ) {}
         }
     }
@@ -127,6 +295,24 @@ mod tests {
             for pattern in expr {
                 "for body"
             }
+
+            for pattern in expr {
+                "for body"
+            } else {
+                "else body"
+            }
+
+            for pattern in expr if guard {
+                "for body"
+            } sep {
+                "separator"
+            } else {
+                "else body"
+            }
+
+            for pattern in expr {}
+            sep {}
+            sep
         }
     }
 
@@ -152,4 +338,425 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_if_let() {
+        snapshot_test! {
+            if let Some(user) = user.get() {
+                (user.name)
+            } else if let Ok(a) | Err(a) = result && a > 0 && let [first, ..] = a.items() {
+                (first)
+            }
+            if (a && b) && let x = y {}
+            if a || b && c {}
+        }
+    }
+
+    #[test]
+    fn test_parse_let() {
+        snapshot_test! {
+            let (first, last) = name.split_once(' ').unwrap();
+            let count: usize = items.len();
+            span { (first) (last) }
+            match count {
+                0 => let x = 1;
+                _ => { let y = count; (y) }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_slot() {
+        snapshot_test! {
+            Layout {
+                slot Header(sticky) { h1 { "Title" } }
+                slot layout::Footer {}
+                slot;
+                slot { "element" }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_children_params() {
+        snapshot_test! {
+            Table(rows=rows) |row| { tr { (row.name) } }
+            Await(future=load()) |(a, b): &(i32, i32), _| { (a + b) }
+            Empty || {}
+        }
+    }
+
+    #[test]
+    fn test_parse_raw() {
+        snapshot_test! {
+            raw "<b>bold</b>"
+            #[sanitize]
+            raw(post.body.clone())
+            raw { "element" }
+            match html {
+                Some(html) => raw(html),
+                None => raw "",
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_macro() {
+        snapshot_test! {
+            icons::check!()
+            p { t!["greeting", name = user.name] }
+            html! { <b>"bold"</b> }
+            match icon {
+                Icon::Check => icons::check!(),
+                Icon::Cross => html! { <i>"x"</i> }
+                _ => {}
+            }
+            path::to::Element {}
+        }
+    }
+
+    #[test]
+    fn test_parse_format_text() {
+        snapshot_test! {
+            "plain {text}"
+            $"Hello, {user.name}! {{escaped}} {count:>3} {a::b(c[0])}"
+            #[fmt]
+            p {
+                "Total: {total:.2}"
+                span { "{x}" }
+                raw "{not formatted}"
+            }
+        }
+
+        // Literals in a placeholder can contain delimiters and `:`
+        let placeholders: [(&str, syn::Expr, Option<&str>); 8] = [
+            (r#"$"{x.get(\"}\")}""#, syn::parse_quote!(x.get("}")), None),
+            (
+                r#"$"{s.trim_start_matches(\"(\")}""#,
+                syn::parse_quote!(s.trim_start_matches("(")),
+                None,
+            ),
+            (
+                r#"$"{s.split(\":\").count():>3}""#,
+                syn::parse_quote!(s.split(":").count()),
+                Some(">3"),
+            ),
+            (
+                r##"$"{x.get(r#\"}\"#)}""##,
+                syn::parse_quote!(x.get(r#"}"#)),
+                None,
+            ),
+            (
+                r#"$"{x.contains('}')}""#,
+                syn::parse_quote!(x.contains('}')),
+                None,
+            ),
+            (
+                r#"$"{['\\'', ':']:?}""#,
+                syn::parse_quote!(['\'', ':']),
+                Some("?"),
+            ),
+            (r#"$"{|v: i32| v}""#, syn::parse_quote!(|v: i32| v), None),
+            (r#"$"{'a: { x }}""#, syn::parse_quote!('a: { x }), None),
+        ];
+        for (source, expr, spec) in placeholders {
+            let nodes = syn::parse_str::<Nodes>(source).unwrap();
+            let NodeType::Text(text_node) = &nodes.nodes[0].node else {
+                panic!("expected a text node for {source}");
+            };
+            let expected = TextSegment::Expr {
+                expr: Box::new(expr),
+                spec: spec.map(str::to_string),
+            };
+            assert_eq!(
+                text_node.segments.as_deref(),
+                Some(&[expected][..]),
+                "parsing {source}"
+            );
+        }
+
+        let errors = [
+            (
+                "$\"{x\"",
+                "unmatched `{` in format string, use `{{` to escape it",
+            ),
+            (
+                "$\"x}\"",
+                "unmatched `}` in format string, use `}}` to escape it",
+            ),
+            (
+                "$\"{}\"",
+                "empty placeholder in format string, positional arguments are not supported",
+            ),
+            (
+                "#[fmt] p { \"{1 +}\" }",
+                "unexpected end of input, expected an expression",
+            ),
+        ];
+        for (source, message) in errors {
+            let error = syn::parse_str::<Nodes>(source).unwrap_err();
+            assert_eq!(error.to_string(), message, "parsing {source}");
+        }
+    }
+
+    #[cfg(all(feature = "printing", feature = "visit-mut"))]
+    #[test]
+    fn test_print_rewritten_format_text() {
+        use crate::visit_mut::VisitMut;
+        use quote::ToTokens;
+
+        struct Rename;
+
+        impl VisitMut for Rename {
+            fn visit_expr_mut(&mut self, i: &mut syn::Expr) {
+                if *i == syn::parse_quote!(name) {
+                    *i = syn::parse_quote!(user.name);
+                }
+            }
+        }
+
+        // Unchanged format strings keep their original literal
+        let mut nodes: Nodes = syn::parse_str(r#"$"{ name } {count:>3}" $"{{x}}""#).unwrap();
+        assert_eq!(
+            nodes.to_token_stream().to_string(),
+            r#"$ "{ name } {count:>3}" $ "{{x}}""#
+        );
+
+        Rename.visit_nodes_mut(&mut nodes);
+        assert_eq!(
+            nodes.to_token_stream().to_string(),
+            r#"$ "{user . name} {count:>3}" $ "{{x}}""#
+        );
+        #[cfg(feature = "fmt")]
+        assert_eq!(
+            crate::fmt::format_nodes(&nodes, &Default::default()),
+            "$\"{user.name} {count:>3}\"\n$\"{{x}}\"\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_void_elements() {
+        snapshot_test! {
+            input(bind:value=value);
+            (value)
+            br
+            img(src="a.png") {}
+            match value {
+                _ => hr,
+            }
+        }
+    }
+
+    #[cfg(feature = "visit")]
+    #[test]
+    fn test_visit_all_node_kinds() {
+        use crate::visit::{self, Visit};
+
+        #[derive(Default)]
+        struct Collect(Vec<String>);
+
+        impl<'ast> Visit<'ast> for Collect {
+            fn visit_node_attr(&mut self, i: &'ast syn::Attribute) {
+                self.0
+                    .push(format!("attr {}", i.path().get_ident().unwrap()));
+            }
+
+            fn visit_expr(&mut self, i: &'ast syn::Expr) {
+                self.0.push(format!("expr {}", quote::quote!(#i)));
+            }
+
+            fn visit_pat(&mut self, i: &'ast syn::Pat) {
+                self.0.push(format!("pat {}", quote::quote!(#i)));
+            }
+
+            fn visit_lit_str(&mut self, i: &'ast syn::LitStr) {
+                self.0.push(format!("text {}", i.value()));
+            }
+
+            fn visit_element(&mut self, i: &'ast Element) {
+                self.0.push("element".to_string());
+                visit::visit_element(self, i);
+            }
+
+            fn visit_macro_node(&mut self, i: &'ast MacroNode) {
+                self.0.push("macro".to_string());
+                visit::visit_macro_node(self, i);
+            }
+        }
+
+        let nodes: Nodes = syn::parse_quote! {
+            #[show]
+            if a {
+                div(class=[b if b0], if b1 { #[cfg(b2)] b3=b4 }) { "c" }
+            } else if let Some(d) = d0 && d {
+                (e)
+            } else {
+                for f in g if f0 { "h" } sep { "h1" } else { "h2" }
+            }
+            match i {
+                _ if j => { "k" }
+            }
+            Layout(l0={ "n0" }, style={ l1: l2 if l3 }) {
+                slot Header(l=m) { "n" }
+            }
+            Table |o| { (p) }
+            raw "q"
+            raw(r)
+            $"{s}t"
+            t!(u)
+        };
+
+        let mut collect = Collect::default();
+        collect.visit_nodes(&nodes);
+        assert_eq!(
+            collect.0,
+            [
+                "attr show",
+                "expr a",
+                "element",
+                "expr b",
+                "expr b0",
+                "expr b1",
+                "attr cfg",
+                "expr b4",
+                "text c",
+                "pat Some (d)",
+                "expr d0",
+                "expr d",
+                "expr e",
+                "pat f",
+                "expr g",
+                "expr f0",
+                "text h",
+                "text h1",
+                "text h2",
+                "expr i",
+                "pat _",
+                "expr j",
+                "text k",
+                "element",
+                "text n0",
+                "expr l2",
+                "expr l3",
+                "expr m",
+                "text n",
+                "element",
+                "pat o",
+                "expr p",
+                "text q",
+                "expr r",
+                "text {s}t",
+                "expr s",
+                "macro",
+            ]
+        );
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn test_fmt() {
+        use crate::fmt::{FormatOptions, format_nodes};
+
+        let nodes: Nodes = syn::parse_quote! {
+            ol.list { #[key(item.clone())] for (i, item) in items.get().into_iter().enumerate() {
+                #[with(let item1 = item.clone();)] #[show] if i % 2 == 0 { li { (item.clone()) } }
+                else if let Some(x) = item.first()&&x.is_empty() {} else { li { (item1.clone()) } } } }
+            let value: RwSignal<String> = RwSignal::new(String::new());
+            form(on:submit=move |e| { e.prevent_default(); items.write().push(value.get()); }, class="form") {
+                input(bind:value=value, "required", ..attrs); br; ::full::path::to::Component::<WithGenerics>("aria-label"="label", title) {}
+                button { "Add Item" }
+            }
+            match length {
+                0 => "Input is required",
+                1 | 2 if allow_short => span {},
+                0..=10 => {},
+                Some(&x) => { "a" (x) }
+                11.. => "That input is too long"
+            }
+            ul { for item in items { li { (item) } } else { li { "No items" } } }
+            p { for tag in tags.iter() if tag.visible && !tag.name.is_empty() { span { (tag.name) } } sep { ", " } }
+            Show(when=ready, fallback={ Spinner(size=2); }) { "Ready" }
+            button(class=["btn","btn-primary" if primary,(extra)]) { "Save" }
+            nav(class=["nav", "nav-open" if open.get(), "nav-dark" if theme.get() == Theme::Dark]) {}
+            div(style={color:"red",margin-top:(px(gap)),}) {} div(style={ display: "none" if hidden, background-color: (theme.get().background()) }) {}
+            Layout { slot Header(sticky=true, class="header") { h1 { "Title" } } slot layout::Footer { "Footer" } p { "Body" } }
+            Table(rows=rows.get()) |(id, row): (u32, Row)| { tr { td { (id) } td { (row.name) } } }
+            p { raw "<b>bold</b>" } #[sanitize] raw(post.body.clone())
+            p { $"Hello, {user.name}!" } #[fmt] p { "Total: {total:.2}" }
+            a(href?=link.url.clone(),"aria-label"?=label) { "Link" }
+            button(#[cfg(debug_assertions)]"data-debug"="true", if collapsible { "aria-expanded"=open.get(), "aria-controls"="menu" }) {}
+            button(if collapsible { "aria-expanded"=open.get(), "aria-controls"="menu", role="menu" }) {}
+            p { icons::check!() } t!["greeting",name=user.name] html!{<b>"bold"</b>}
+        };
+
+        let options = FormatOptions {
+            max_width: 60,
+            indent_width: 2,
+        };
+        let formatted = format_nodes(&nodes, &options);
+        insta::assert_snapshot!(formatted);
+
+        // Formatting normalizes trailing commas, so only check that it is stable
+        let reparsed: Nodes = syn::parse_str(&formatted).unwrap();
+        assert_eq!(format_nodes(&reparsed, &options), formatted);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        let source = r#"#[show]
+if ok {
+    a.link.is-active #go(href=url, title?=tip, #[cfg(test)] if ok { role="link" }, "aria-label"="link", on:click=move |_| go(), ..attrs) { "go" }
+} else {
+    for (i, item) in items if i > 0 { (item) } sep { ", " } else { "empty" }
+    match i { 0 => {}, _ if i > 1 => "many" }
+}
+if let Some(user) = user && user.admin { "admin" }
+Card(footer={ "footer" }, class=["card", "active" if active], style={ margin-top: "1em" if active }) { slot Title(level=2) { "title" } }
+Table(rows) |row, i: usize| { (i) }
+#[sanitize] raw(html) raw "<b>hi</b>"
+$"{count:>3} left" #[fmt] { "{{x}}" }
+icons::check!() t!["greeting", name = user.name]
+"#;
+        let (nodes, _) = crate::parse_str::<Nodes>(source).unwrap();
+        let json = serde_json::to_string_pretty(&nodes).unwrap();
+        insta::assert_snapshot!(json);
+    }
+
+    #[cfg(feature = "source-map")]
+    #[test]
+    fn test_source_map() {
+        let source = "div {\n    \"héllo\"\n    span(x=) {}\n}\n";
+
+        let (recovered, source_map) = crate::parse_str::<Recovered<Nodes>>(source).unwrap();
+        let [error] = &recovered.errors[..] else {
+            panic!("expected a single error");
+        };
+        assert_eq!(source_map.source_text(error.span()), Some(")"));
+        let start = source_map.start(error.span());
+        assert_eq!((start.line, start.column), (3, 11));
+
+        let path = std::env::temp_dir().join("rdml_test_source_map.rdml");
+        std::fs::write(&path, source).unwrap();
+        let error = crate::parse_file::<Nodes>(&path).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unexpected end of input, expected an expression"
+        );
+
+        let text_span = match &recovered.value.nodes[0].node {
+            NodeType::Element(div) => match &div.children.as_ref().unwrap().nodes[0].node {
+                NodeType::Text(text) => text.lit.span(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        assert_eq!(source_map.byte_range(text_span), 10..18);
+        let end = source_map.end(text_span);
+        assert_eq!((end.line, end.column), (2, 11));
+
+        // A span from another source can lie outside of this source
+        let other = crate::SourceMap::new("div {}");
+        assert_eq!(other.source_text(text_span), None);
+    }
 }
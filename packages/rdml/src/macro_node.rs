@@ -0,0 +1,58 @@
+use syn::{
+    Macro, MacroDelimiter, Path, Result, Token,
+    parse::{Parse, ParseStream},
+    token::{Brace, Bracket, Paren},
+};
+
+/// A macro invocation node: `icons::check!()`
+///
+/// The macro is passed through by backends as an expression. Any delimiter can be used, and a
+/// path followed by a `!` is always parsed as a macro rather than as an element.
+///
+/// # Example
+/// ```ignore
+/// icons::check!()
+/// t!["greeting", name = user.name]
+/// html! { <b>bold</b> }
+/// ```
+#[derive(Debug, PartialEq, Hash)]
+pub struct MacroNode {
+    pub mac: Macro,
+}
+
+impl MacroNode {
+    /// Whether the input starts with a macro invocation
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        fork.call(Path::parse_mod_style).is_ok()
+            && fork.peek(Token![!])
+            && (fork.peek2(Paren) || fork.peek2(Bracket) || fork.peek2(Brace))
+    }
+
+    /// Whether the macro is invoked with braces: `html! { ... }`
+    pub fn is_braced(&self) -> bool {
+        matches!(self.mac.delimiter, MacroDelimiter::Brace(_))
+    }
+}
+
+impl Parse for MacroNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            mac: input.parse()?,
+        })
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for MacroNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.mac.to_tokens(tokens);
+        }
+    }
+}
@@ -78,14 +78,50 @@ impl Parse for MatchNodeArm {
     }
 }
 
-fn requires_comma_to_be_match_arm(body: &Node) -> bool {
+pub(crate) fn requires_comma_to_be_match_arm(body: &Node) -> bool {
     match &body.node {
-        NodeType::Element(_)
-        | NodeType::If(_)
+        NodeType::Element(element) => element.children.is_none() && element.semi_token.is_none(),
+
+        NodeType::If(_)
         | NodeType::For(_)
         | NodeType::Match(_)
+        | NodeType::Let(_)
+        | NodeType::Slot(_)
         | NodeType::Block(_) => false,
 
-        NodeType::Expr(_) | NodeType::Text(_) => true,
+        NodeType::Macro(macro_node) => !macro_node.is_braced(),
+
+        NodeType::Expr(_) | NodeType::Text(_) | NodeType::Raw(_) => true,
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt};
+
+    use super::*;
+
+    impl ToTokens for MatchNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.match_token.to_tokens(tokens);
+            self.expr.to_tokens(tokens);
+            self.brace_token.surround(tokens, |tokens| {
+                tokens.append_all(&self.arms);
+            });
+        }
+    }
+
+    impl ToTokens for MatchNodeArm {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.pat.to_tokens(tokens);
+            if let Some((if_token, guard)) = &self.guard {
+                if_token.to_tokens(tokens);
+                guard.to_tokens(tokens);
+            }
+            self.fat_arrow_token.to_tokens(tokens);
+            self.body.to_tokens(tokens);
+            self.comma.to_tokens(tokens);
+        }
     }
 }
@@ -1,10 +1,13 @@
 use syn::{
-    Attribute, Expr, LitStr, Result, Token, parenthesized,
+    Attribute, Expr, Ident, LitStr, Result, Token, parenthesized,
     parse::{Parse, ParseStream},
     token::{Brace, Paren},
 };
 
-use crate::{Block, Element, ForNode, IfNode, MatchNode};
+use crate::{
+    Block, Element, ForNode, IfNode, LetNode, MacroNode, MatchNode, RawNode, SlotNode, TextNode,
+    helpers::with_format_text, kw,
+};
 
 /// An expression interpolation node: `(1 + 1)`.
 #[derive(Debug, PartialEq, Hash)]
@@ -29,8 +32,8 @@ pub enum NodeType {
     /// Element: `div {}`
     Element(Element),
 
-    /// Text literal: `"Hello, world!"`
-    Text(LitStr),
+    /// Text literal: `"Hello, world!"` or `$"Hello, {name}!"`
+    Text(TextNode),
 
     /// Expression literal: `(1 + 1)`
     Expr(ExprNode),
@@ -44,6 +47,18 @@ pub enum NodeType {
     /// Match node: `match expr { [...] }`
     Match(MatchNode),
 
+    /// Let node: `let pattern = expr;`
+    Let(LetNode),
+
+    /// Slot node: `slot Name { [...] }`
+    Slot(SlotNode),
+
+    /// Raw html node: `raw "<b>bold</b>"` or `raw(expr)`
+    Raw(RawNode),
+
+    /// Macro invocation: `path!(...)`, `path![...]` or `path! { ... }`
+    Macro(MacroNode),
+
     /// A block fragment: `{ div {} span {} [...] }`
     Block(Block),
 }
@@ -56,12 +71,20 @@ impl Parse for NodeType {
             Ok(Self::For(input.parse()?))
         } else if input.peek(Token![match]) {
             Ok(Self::Match(input.parse()?))
-        } else if input.peek(LitStr) {
+        } else if input.peek(Token![let]) {
+            Ok(Self::Let(input.parse()?))
+        } else if input.peek(kw::slot) && (input.peek2(Ident) || input.peek2(Token![::])) {
+            Ok(Self::Slot(input.parse()?))
+        } else if input.peek(kw::raw) && (input.peek2(LitStr) || input.peek2(Paren)) {
+            Ok(Self::Raw(input.parse()?))
+        } else if input.peek(LitStr) || (input.peek(Token![$]) && input.peek2(LitStr)) {
             Ok(Self::Text(input.parse()?))
         } else if input.peek(Brace) {
             Ok(Self::Block(input.parse()?))
         } else if input.peek(Paren) {
             Ok(Self::Expr(input.parse()?))
+        } else if MacroNode::peek(input) {
+            Ok(Self::Macro(input.parse()?))
         } else {
             Ok(Self::Element(input.parse()?))
         }
@@ -69,6 +92,9 @@ impl Parse for NodeType {
 }
 
 /// A node with attributes
+///
+/// Attributes are left to backends to interpret, except for `#[fmt]` which makes every text
+/// literal inside of the node a format string, see [`TextNode`].
 #[derive(Debug, PartialEq, Hash)]
 pub struct Node {
     pub attrs: Vec<Attribute>,
@@ -77,9 +103,53 @@ pub struct Node {
 
 impl Parse for Node {
     fn parse(input: ParseStream) -> Result<Self> {
-        Ok(Node {
-            attrs: input.call(Attribute::parse_outer)?,
-            node: input.parse()?,
-        })
+        let attrs = input.call(Attribute::parse_outer)?;
+        let node = if attrs.iter().any(|attr| attr.path().is_ident("fmt")) {
+            with_format_text(|| input.parse())?
+        } else {
+            input.parse()?
+        };
+        Ok(Node { attrs, node })
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::{ToTokens, TokenStreamExt};
+
+    use super::*;
+
+    impl ToTokens for ExprNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.paren_token.surround(tokens, |tokens| {
+                self.expr.to_tokens(tokens);
+            });
+        }
+    }
+
+    impl ToTokens for NodeType {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                NodeType::Element(element) => element.to_tokens(tokens),
+                NodeType::Text(text_node) => text_node.to_tokens(tokens),
+                NodeType::Expr(expr_node) => expr_node.to_tokens(tokens),
+                NodeType::If(if_node) => if_node.to_tokens(tokens),
+                NodeType::For(for_node) => for_node.to_tokens(tokens),
+                NodeType::Match(match_node) => match_node.to_tokens(tokens),
+                NodeType::Let(let_node) => let_node.to_tokens(tokens),
+                NodeType::Slot(slot_node) => slot_node.to_tokens(tokens),
+                NodeType::Raw(raw_node) => raw_node.to_tokens(tokens),
+                NodeType::Macro(macro_node) => macro_node.to_tokens(tokens),
+                NodeType::Block(block) => block.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for Node {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            tokens.append_all(&self.attrs);
+            self.node.to_tokens(tokens);
+        }
     }
 }
@@ -0,0 +1,77 @@
+use syn::{
+    LitStr, Result,
+    parse::{Parse, ParseStream},
+};
+
+use crate::{ExprNode, kw};
+
+/// The html of a [`RawNode`]
+#[derive(Debug, PartialEq, Hash)]
+pub enum RawContent {
+    /// Literal html: `"<b>bold</b>"`
+    Text(LitStr),
+
+    /// An expression evaluating to html: `(post.body)`
+    Expr(Box<ExprNode>),
+}
+
+impl Parse for RawContent {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            Ok(Self::Text(input.parse()?))
+        } else {
+            Ok(Self::Expr(input.parse()?))
+        }
+    }
+}
+
+/// A raw html node, rendered without escaping
+///
+/// Backends may support a `#[sanitize]` node attribute to only allow safe html, see
+/// [`sanitize`](crate::sanitize). Since `raw` is also a valid element name, a `raw` followed by a
+/// string literal or parentheses is always parsed as a raw node.
+///
+/// # Example
+/// ```ignore
+/// raw "<b>bold</b>"
+/// #[sanitize]
+/// raw(post.body.clone())
+/// ```
+#[derive(Debug, PartialEq, Hash)]
+pub struct RawNode {
+    pub raw_token: kw::raw,
+    pub content: RawContent,
+}
+
+impl Parse for RawNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            raw_token: input.parse()?,
+            content: input.parse()?,
+        })
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for RawContent {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            match self {
+                RawContent::Text(lit_str) => lit_str.to_tokens(tokens),
+                RawContent::Expr(expr_node) => expr_node.to_tokens(tokens),
+            }
+        }
+    }
+
+    impl ToTokens for RawNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.raw_token.to_tokens(tokens);
+            self.content.to_tokens(tokens);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use syn::{
+    Error, Result,
+    parse::{Parse, ParseStream},
+};
+
+use crate::helpers::with_recovery;
+
+/// The result of parsing with error recovery.
+///
+/// While parsing a `Recovered<T>`, a node (or match arm) that fails to parse is skipped up to
+/// its next sibling or the closing brace of its block, and its error is recorded instead of
+/// aborting the parse. This makes it possible to report every syntax error in a template at
+/// once.
+///
+/// # Example
+///
+/// ```
+/// use rdml::{Nodes, Recovered};
+///
+/// let recovered: Recovered<Nodes> = syn::parse_quote! {
+///     div(class=) {}
+///     span { "ok" }
+///     p { if {} }
+/// };
+///
+/// assert_eq!(recovered.value.nodes.len(), 2);
+/// assert_eq!(recovered.errors.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Recovered<T> {
+    /// The syntax tree, without the nodes that failed to parse
+    pub value: T,
+
+    /// Every syntax error that was recovered from, in source order
+    pub errors: Vec<Error>,
+}
+
+impl<T> Recovered<T> {
+    /// All recovered errors combined into a single [`Error`], if there were any
+    pub fn error(&self) -> Option<Error> {
+        combine_errors(self.errors.iter().cloned())
+    }
+
+    /// Converts into a [`Result`], failing with every recovered error if there were any
+    pub fn into_result(self) -> Result<T> {
+        match combine_errors(self.errors) {
+            Some(error) => Err(error),
+            None => Ok(self.value),
+        }
+    }
+}
+
+impl<T: Parse> Parse for Recovered<T> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        match with_recovery(|| input.parse()) {
+            (Ok(value), errors) => Ok(Self { value, errors }),
+            (Err(error), errors) => Err(combine_errors(errors.into_iter().chain([error])).unwrap()),
+        }
+    }
+}
+
+fn combine_errors(errors: impl IntoIterator<Item = Error>) -> Option<Error> {
+    errors.into_iter().reduce(|mut combined, error| {
+        combined.combine(error);
+        combined
+    })
+}
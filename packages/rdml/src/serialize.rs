@@ -0,0 +1,628 @@
+//! [`serde`] serialization of rdml syntax trees, for tooling written in other languages.
+//!
+//! [`Nodes`] serializes to a document carrying the [`SCHEMA_VERSION`], every other syntax tree
+//! type serializes to the bare shape described below. Embedded rust syntax (expressions,
+//! patterns, paths and node attributes) is rendered as source text rather than as a syntax
+//! tree.
+//!
+//! # Schema
+//!
+//! ```text
+//! Document  = { "version": 1, "nodes": [Node] }
+//!
+//! Node      = { "kind": "element", "name": Rust, "classes": [Selector], "id": Selector | null,
+//!               "attributes": [AttributeEntry] | null, "params": [Rust] | null,
+//!               "children": [Node] | null, ...Common }
+//!           | { "kind": "text", "value": string, "segments": [TextSegment] | null, ...Common }
+//!           | { "kind": "expr", "expr": Rust, ...Common }
+//!           | { "kind": "if", "cond": [Condition], "then": [Node], "else": Else | null,
+//!               ...Common }
+//!           | { "kind": "for", "pat": Rust, "expr": Rust, "guard": Rust | null, "body": [Node],
+//!               "sep": [Node] | null, "else": [Node] | null, ...Common }
+//!           | { "kind": "match", "expr": Rust, "arms": [MatchArm], ...Common }
+//!           | { "kind": "block", "nodes": [Node], ...Common }
+//!           | { "kind": "let", "pat": Rust, "expr": Rust, ...Common }
+//!           | { "kind": "slot", "name": Rust, "attributes": [AttributeEntry] | null,
+//!               "children": [Node], ...Common }
+//!           | { "kind": "raw", "content": RawContent, ...Common }
+//!           | { "kind": "macro", "macro": Rust, ...Common }
+//! Common    = "attrs": [Rust], "span": Span
+//!
+//! Else      = { "kind": "if", "cond": [Condition], "then": [Node], "else": Else | null,
+//!               "span": Span }
+//!           | { "kind": "else", "nodes": [Node], "span": Span }
+//! Condition = { "kind": "expr", "expr": Rust }
+//!           | { "kind": "let", "pat": Rust, "expr": Rust, "span": Span }
+//! TextSegment
+//!           = { "kind": "text", "value": string }
+//!           | { "kind": "expr", "expr": Rust, "spec": string | null }
+//! RawContent
+//!           = { "kind": "text", "value": string, "span": Span }
+//!           | { "kind": "expr", "expr": Rust, "span": Span }
+//! MatchArm  = { "pat": Rust, "guard": Rust | null, "body": Node, "span": Span }
+//!
+//! AttributeEntry
+//!           = { "kind": "attribute", ...Attribute, "attrs": [Rust] }
+//!           | { "kind": "spread", "expr": Rust, "attrs": [Rust], "span": Span }
+//!           | { "kind": "if", "cond": Rust, "attributes": [AttributeEntry], "attrs": [Rust],
+//!               "span": Span }
+//! Attribute = { "name": AttributeName, "optional": bool, "value": AttributeValue | null,
+//!               "span": Span }
+//! AttributeValue
+//!           = { "kind": "expr", "expr": Rust }
+//!           | { "kind": "markup", "nodes": [Node], "span": Span }
+//!           | { "kind": "classes", "classes": [ClassListEntry], "span": Span }
+//!           | { "kind": "style", "properties": [StyleProperty], "span": Span }
+//! ClassListEntry
+//!           = { "class": Rust, "if": Rust | null, "span": Span }
+//! StyleProperty
+//!           = { "name": string, "value": Rust, "if": Rust | null, "span": Span }
+//! AttributeName
+//!           = { "kind": "single", "name": string, "span": Span }
+//!           | { "kind": "quoted", "name": string, "span": Span }
+//!           | { "kind": "directive", "directive": string, "name": string, "span": Span }
+//!
+//! Selector  = { "name": string, "span": Span }
+//! Rust      = { "source": string, "span": Span }
+//! Span      = { "start": Position, "end": Position }
+//! Position  = { "offset": number, "line": number, "column": number }
+//! ```
+//!
+//! `attrs` holds the outer attributes of a node (e.g. `#[show]`) in source order, they are not
+//! covered by the span of the node. The outer attributes of an attribute list entry (e.g.
+//! `#[cfg(test)]`) are covered by the span of the entry. The `value` of an attribute is `null` if
+//! it was omitted, which makes a single name punned and a quoted or directive name boolean. Span
+//! positions follow [`proc_macro2::LineColumn`]: lines are 1-indexed and columns are 0-indexed
+//! characters, while offsets are in bytes. Spans are only meaningful for syntax trees parsed
+//! outside of a procedural macro, e.g. with [`parse_str`](crate::parse_str).
+//!
+//! # Versioning
+//!
+//! [`SCHEMA_VERSION`] is incremented whenever the shape of the output changes after a release,
+//! including when new node kinds are added.
+//!
+//!  - 1: initial schema
+//!
+//! # Example
+//!
+//! ```
+//! let (nodes, _): (rdml::Nodes, _) = rdml::parse_str(r#"p { "hi" }"#).unwrap();
+//! let json = serde_json::to_value(&nodes).unwrap();
+//!
+//! assert_eq!(json["version"], rdml::serialize::SCHEMA_VERSION);
+//! assert_eq!(json["nodes"][0]["kind"], "element");
+//! assert_eq!(json["nodes"][0]["children"][0]["value"], "hi");
+//! ```
+
+use proc_macro2::{LineColumn, Span};
+use quote::ToTokens;
+use serde::{
+    Serialize, Serializer,
+    ser::{SerializeMap, SerializeSeq},
+};
+use syn::{Expr, spanned::Spanned};
+
+use crate::{
+    Attribute, AttributeEntry, AttributeName, AttributeNameDirective, AttributeValue, Attributes,
+    Block, ClassListEntry, ConditionClause, ConditionalAttributes, Element, ElementClass,
+    ElementId, ElseNode, ExprNode, ForNode, IfNode, LetCondition, LetNode, MacroNode, MatchNode,
+    MatchNodeArm, Node, NodeType, Nodes, RawContent, RawNode, SlotNode, SpreadAttribute,
+    StyleProperty, TextNode, TextSegment,
+    fmt::{expr_to_string, tokens_to_string},
+};
+
+/// The version of the serialized schema, stored in the `version` field of a serialized
+/// [`Nodes`] document.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Writes the fields of a syntax tree type into a map, so that [`Node`] can add its own fields
+/// to the map of its [`NodeType`].
+trait Entries {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error>;
+}
+
+macro_rules! serialize_entries {
+    ($($ty:ty),* $(,)?) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(None)?;
+                self.entries(&mut map)?;
+                map.end()
+            }
+        }
+    )*};
+}
+
+serialize_entries!(
+    Node,
+    NodeType,
+    Block,
+    Element,
+    ElementClass,
+    ElementId,
+    AttributeEntry,
+    Attribute,
+    SpreadAttribute,
+    ConditionalAttributes,
+    AttributeName,
+    AttributeNameDirective,
+    AttributeValue,
+    ClassListEntry,
+    StyleProperty,
+    ExprNode,
+    IfNode,
+    ElseNode,
+    ConditionClause,
+    LetCondition,
+    ForNode,
+    MatchNode,
+    MatchNodeArm,
+    LetNode,
+    SlotNode,
+    RawNode,
+    RawContent,
+    TextNode,
+    TextSegment,
+    MacroNode,
+);
+
+struct SpanRange(Span);
+
+impl Serialize for SpanRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let range = self.0.byte_range();
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("start", &Position(range.start, self.0.start()))?;
+        map.serialize_entry("end", &Position(range.end, self.0.end()))?;
+        map.end()
+    }
+}
+
+struct Position(usize, LineColumn);
+
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("offset", &self.0)?;
+        map.serialize_entry("line", &self.1.line)?;
+        map.serialize_entry("column", &self.1.column)?;
+        map.end()
+    }
+}
+
+/// Embedded rust syntax, rendered as source text
+struct Rust(String, Span);
+
+impl Rust {
+    fn expr(expr: &Expr) -> Self {
+        Self(expr_to_string(expr), expr.span())
+    }
+
+    fn tokens(tokens: &impl ToTokens) -> Self {
+        let tokens = tokens.to_token_stream();
+        let span = tokens.span();
+        Self(tokens_to_string(tokens), span)
+    }
+}
+
+impl Serialize for Rust {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("source", &self.0)?;
+        map.serialize_entry("span", &SpanRange(self.1))?;
+        map.end()
+    }
+}
+
+fn span_entry<M: SerializeMap>(map: &mut M, tokens: &impl ToTokens) -> Result<(), M::Error> {
+    map.serialize_entry("span", &SpanRange(tokens.to_token_stream().span()))
+}
+
+impl Serialize for Nodes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("version", &SCHEMA_VERSION)?;
+        map.serialize_entry("nodes", &self.nodes)?;
+        map.end()
+    }
+}
+
+impl Entries for Node {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        self.node.entries(map)?;
+        let attrs = self.attrs.iter().map(Rust::tokens).collect::<Vec<_>>();
+        map.serialize_entry("attrs", &attrs)
+    }
+}
+
+impl Entries for NodeType {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            NodeType::Element(element) => {
+                map.serialize_entry("kind", "element")?;
+                element.entries(map)
+            }
+            NodeType::Text(text_node) => {
+                map.serialize_entry("kind", "text")?;
+                text_node.entries(map)
+            }
+            NodeType::Expr(expr_node) => {
+                map.serialize_entry("kind", "expr")?;
+                expr_node.entries(map)
+            }
+            NodeType::If(if_node) => {
+                map.serialize_entry("kind", "if")?;
+                if_node.entries(map)
+            }
+            NodeType::For(for_node) => {
+                map.serialize_entry("kind", "for")?;
+                for_node.entries(map)
+            }
+            NodeType::Match(match_node) => {
+                map.serialize_entry("kind", "match")?;
+                match_node.entries(map)
+            }
+            NodeType::Block(block) => {
+                map.serialize_entry("kind", "block")?;
+                block.entries(map)
+            }
+            NodeType::Let(let_node) => {
+                map.serialize_entry("kind", "let")?;
+                let_node.entries(map)
+            }
+            NodeType::Slot(slot_node) => {
+                map.serialize_entry("kind", "slot")?;
+                slot_node.entries(map)
+            }
+            NodeType::Raw(raw_node) => {
+                map.serialize_entry("kind", "raw")?;
+                raw_node.entries(map)
+            }
+            NodeType::Macro(macro_node) => {
+                map.serialize_entry("kind", "macro")?;
+                macro_node.entries(map)
+            }
+        }
+    }
+}
+
+impl Entries for Block {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("nodes", &self.nodes)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for Element {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("name", &Rust::tokens(&self.path))?;
+        map.serialize_entry("classes", &self.classes)?;
+        map.serialize_entry("id", &self.id)?;
+        map.serialize_entry("attributes", &self.attributes)?;
+        map.serialize_entry(
+            "params",
+            &self
+                .children_params
+                .as_ref()
+                .map(|it| it.inputs.iter().map(Rust::tokens).collect::<Vec<_>>()),
+        )?;
+        map.serialize_entry("children", &self.children.as_ref().map(|it| &it.nodes))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for ElementClass {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("name", &self.name.to_string())?;
+        span_entry(map, &self.name)
+    }
+}
+
+impl Entries for ElementId {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("name", &self.name.to_string())?;
+        span_entry(map, &self.name)
+    }
+}
+
+impl Serialize for Attributes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.attributes.len()))?;
+        for attribute in &self.attributes {
+            seq.serialize_element(attribute)?;
+        }
+        seq.end()
+    }
+}
+
+impl Entries for Attribute {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("name", &self.name)?;
+        map.serialize_entry("optional", &self.is_optional())?;
+        map.serialize_entry("value", &self.value)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for AttributeValue {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            AttributeValue::Expr(expr) => {
+                map.serialize_entry("kind", "expr")?;
+                map.serialize_entry("expr", &Rust::expr(expr))
+            }
+            AttributeValue::Markup(block) => {
+                map.serialize_entry("kind", "markup")?;
+                block.entries(map)
+            }
+            AttributeValue::ClassList(class_list) => {
+                map.serialize_entry("kind", "classes")?;
+                let classes = class_list.entries.iter().collect::<Vec<_>>();
+                map.serialize_entry("classes", &classes)?;
+                span_entry(map, class_list)
+            }
+            AttributeValue::Style(style) => {
+                map.serialize_entry("kind", "style")?;
+                let properties = style.properties.iter().collect::<Vec<_>>();
+                map.serialize_entry("properties", &properties)?;
+                span_entry(map, style)
+            }
+        }
+    }
+}
+
+impl Entries for ClassListEntry {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("class", &Rust::expr(&self.class))?;
+        map.serialize_entry(
+            "if",
+            &self
+                .condition
+                .as_ref()
+                .map(|(_, condition)| Rust::expr(condition)),
+        )?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for StyleProperty {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("name", &self.name.value())?;
+        map.serialize_entry("value", &Rust::expr(&self.value))?;
+        map.serialize_entry(
+            "if",
+            &self
+                .condition
+                .as_ref()
+                .map(|(_, condition)| Rust::expr(condition)),
+        )?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for AttributeEntry {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            AttributeEntry::Attribute(attribute) => {
+                map.serialize_entry("kind", "attribute")?;
+                attribute.entries(map)?;
+            }
+            AttributeEntry::Spread(spread) => {
+                map.serialize_entry("kind", "spread")?;
+                spread.entries(map)?;
+            }
+            AttributeEntry::Conditional(conditional) => {
+                map.serialize_entry("kind", "if")?;
+                conditional.entries(map)?;
+            }
+        }
+        let attrs = self.attrs().iter().map(Rust::tokens).collect::<Vec<_>>();
+        map.serialize_entry("attrs", &attrs)
+    }
+}
+
+impl Entries for ConditionalAttributes {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("cond", &Rust::expr(&self.cond))?;
+        let attributes = self.attributes.iter().collect::<Vec<_>>();
+        map.serialize_entry("attributes", &attributes)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for SpreadAttribute {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("expr", &Rust::expr(&self.expr))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for AttributeName {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            AttributeName::Single(path) => {
+                map.serialize_entry("kind", "single")?;
+                map.serialize_entry("name", &tokens_to_string(path.to_token_stream()))?;
+                span_entry(map, path)
+            }
+            AttributeName::Quoted(lit_str) => {
+                map.serialize_entry("kind", "quoted")?;
+                map.serialize_entry("name", &lit_str.value())?;
+                span_entry(map, lit_str)
+            }
+            AttributeName::Directive(directive) => directive.entries(map),
+        }
+    }
+}
+
+impl Entries for AttributeNameDirective {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("kind", "directive")?;
+        map.serialize_entry("directive", &self.directive.to_string())?;
+        map.serialize_entry("name", &tokens_to_string(self.path.to_token_stream()))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for ExprNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("expr", &Rust::expr(&self.expr))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for IfNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        let cond = self.cond.clauses.iter().collect::<Vec<_>>();
+        map.serialize_entry("cond", &cond)?;
+        map.serialize_entry("then", &self.then_branch.nodes)?;
+        map.serialize_entry("else", &self.else_branch.as_ref().map(|(_, it)| it))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for ElseNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            ElseNode::If(if_node) => {
+                map.serialize_entry("kind", "if")?;
+                if_node.entries(map)
+            }
+            ElseNode::Else(block) => {
+                map.serialize_entry("kind", "else")?;
+                block.entries(map)
+            }
+        }
+    }
+}
+
+impl Entries for ConditionClause {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            ConditionClause::Expr(expr) => {
+                map.serialize_entry("kind", "expr")?;
+                map.serialize_entry("expr", &Rust::expr(expr))
+            }
+            ConditionClause::Let(let_condition) => {
+                map.serialize_entry("kind", "let")?;
+                let_condition.entries(map)
+            }
+        }
+    }
+}
+
+impl Entries for LetCondition {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("pat", &Rust::tokens(&self.pat))?;
+        map.serialize_entry("expr", &Rust::expr(&self.expr))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for ForNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("pat", &Rust::tokens(&self.pat))?;
+        map.serialize_entry("expr", &Rust::expr(&self.expr))?;
+        map.serialize_entry(
+            "guard",
+            &self.guard.as_ref().map(|(_, guard)| Rust::expr(guard)),
+        )?;
+        map.serialize_entry("body", &self.body.nodes)?;
+        let separator = self.separator.as_ref().map(|(_, block)| &block.nodes);
+        map.serialize_entry("sep", &separator)?;
+        let else_branch = self.else_branch.as_ref().map(|(_, block)| &block.nodes);
+        map.serialize_entry("else", &else_branch)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for MatchNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("expr", &Rust::expr(&self.expr))?;
+        map.serialize_entry("arms", &self.arms)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for MatchNodeArm {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("pat", &Rust::tokens(&self.pat))?;
+        map.serialize_entry(
+            "guard",
+            &self.guard.as_ref().map(|(_, guard)| Rust::expr(guard)),
+        )?;
+        map.serialize_entry("body", &self.body)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for LetNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("pat", &Rust::tokens(&self.pat))?;
+        map.serialize_entry("expr", &Rust::expr(&self.expr))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for SlotNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("name", &Rust::tokens(&self.path))?;
+        map.serialize_entry("attributes", &self.attributes)?;
+        map.serialize_entry("children", &self.children.nodes)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for RawNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("content", &self.content)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for RawContent {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            RawContent::Text(lit_str) => {
+                map.serialize_entry("kind", "text")?;
+                map.serialize_entry("value", &lit_str.value())?;
+                span_entry(map, lit_str)
+            }
+            RawContent::Expr(expr_node) => {
+                map.serialize_entry("kind", "expr")?;
+                expr_node.entries(map)
+            }
+        }
+    }
+}
+
+impl Entries for MacroNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("macro", &Rust::tokens(&self.mac))?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for TextNode {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry("value", &self.lit.value())?;
+        map.serialize_entry("segments", &self.segments)?;
+        span_entry(map, self)
+    }
+}
+
+impl Entries for TextSegment {
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            TextSegment::Text(text) => {
+                map.serialize_entry("kind", "text")?;
+                map.serialize_entry("value", text)
+            }
+            TextSegment::Expr { expr, spec } => {
+                map.serialize_entry("kind", "expr")?;
+                map.serialize_entry("expr", &Rust::expr(expr))?;
+                map.serialize_entry("spec", spec)
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use syn::{
+    Path, Result,
+    parse::{Parse, ParseStream},
+    token::Paren,
+};
+
+use crate::{Attributes, Block, kw};
+
+/// A named slot, passing a region of markup to the component it is a child of
+///
+/// Since `slot` is also the name of an html element, a `slot` followed by a path is always
+/// parsed as a slot node. An element named `slot` without children followed by another element
+/// must end with a `;`.
+///
+/// # Example
+/// ```ignore
+/// Layout {
+///     slot Header(sticky=true) { h1 { "Title" } }
+///     slot Footer { "Footer" }
+///     p { "Body" }
+/// }
+/// ```
+#[derive(Debug, PartialEq, Hash)]
+pub struct SlotNode {
+    pub slot_token: kw::slot,
+    /// The name of the slot
+    pub path: Path,
+    pub attributes: Option<Attributes>,
+    pub children: Block,
+}
+
+impl Parse for SlotNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            slot_token: input.parse()?,
+            path: input.parse()?,
+            attributes: input.peek(Paren).then(|| input.parse()).transpose()?,
+            children: input.parse()?,
+        })
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for SlotNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.slot_token.to_tokens(tokens);
+            self.path.to_tokens(tokens);
+            self.attributes.to_tokens(tokens);
+            self.children.to_tokens(tokens);
+        }
+    }
+}
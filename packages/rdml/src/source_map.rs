@@ -0,0 +1,156 @@
+use std::{
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::{LineColumn, Span, TokenStream};
+use syn::{Error, Result, parse::Parse};
+
+/// Template source text, used to resolve the spans of a syntax tree parsed from it to byte
+/// offsets and line/column positions.
+///
+/// This only works outside of a procedural macro, where spans refer to the parsed string
+/// rather than to the macro call site. Spans are resolved relative to the string they were
+/// parsed from, so a span from another source resolves to unrelated text.
+///
+/// # Example
+///
+/// ```
+/// use rdml::{Nodes, NodeType, SourceMap};
+///
+/// let source_map = SourceMap::new("div {\n    \"hello\"\n}");
+/// let nodes: Nodes = source_map.parse().unwrap();
+///
+/// let NodeType::Element(div) = &nodes.nodes[0].node else { unreachable!() };
+/// let NodeType::Text(text) = &div.children.as_ref().unwrap().nodes[0].node else {
+///     unreachable!()
+/// };
+/// let span = text.lit.span();
+///
+/// assert_eq!(source_map.source_text(span), Some("\"hello\""));
+/// assert_eq!(source_map.byte_range(span), 10..17);
+/// assert_eq!(source_map.start(span).line, 2);
+/// assert_eq!(source_map.start(span).column, 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceMap {
+    source: String,
+    path: Option<PathBuf>,
+    /// The byte offset of the start of each line
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    /// Creates a source map for template source text
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let line_starts = [0]
+            .into_iter()
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self {
+            source,
+            path: None,
+            line_starts,
+        }
+    }
+
+    /// Reads a template file into a source map
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        Ok(Self {
+            path: Some(path.to_owned()),
+            ..Self::new(fs::read_to_string(path)?)
+        })
+    }
+
+    /// Tokenizes and parses the source text
+    pub fn parse<T: Parse>(&self) -> Result<T> {
+        let tokens: TokenStream = self.source.parse()?;
+        syn::parse2(tokens)
+    }
+
+    /// The source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The path of the file that the source was read from, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The byte offsets of a span of this source
+    ///
+    /// The offsets are relative to the string the span was parsed from, and aren't checked to
+    /// lie within this source.
+    pub fn byte_range(&self, span: Span) -> Range<usize> {
+        span.byte_range()
+    }
+
+    /// The source text of a span of this source, or `None` if the byte range of the span does not
+    /// lie within this source or is not on character boundaries
+    pub fn source_text(&self, span: Span) -> Option<&str> {
+        self.source.get(self.byte_range(span))
+    }
+
+    /// The line/column position of the start of a span of this source
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span does not belong to this source, see [`line_column`](Self::line_column).
+    pub fn start(&self, span: Span) -> LineColumn {
+        self.line_column(self.byte_range(span).start)
+    }
+
+    /// The line/column position of the end of a span of this source
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span does not belong to this source, see [`line_column`](Self::line_column).
+    pub fn end(&self, span: Span) -> LineColumn {
+        self.line_column(self.byte_range(span).end)
+    }
+
+    /// The line/column position of a byte offset into the source.
+    ///
+    /// As with [`proc_macro2`], lines are 1-indexed and columns are 0-indexed and count
+    /// characters rather than bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is past the end of the source or is not on a character boundary.
+    pub fn line_column(&self, offset: usize) -> LineColumn {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        LineColumn {
+            line,
+            column: self.source[line_start..offset].chars().count(),
+        }
+    }
+}
+
+/// Parses template source text, returning the syntax tree and a [`SourceMap`] to resolve its
+/// spans.
+///
+/// ```
+/// let (nodes, source_map): (rdml::Nodes, _) = rdml::parse_str("div {}").unwrap();
+/// ```
+pub fn parse_str<T: Parse>(source: &str) -> Result<(T, SourceMap)> {
+    let source_map = SourceMap::new(source);
+    Ok((source_map.parse()?, source_map))
+}
+
+/// Reads and parses a template file, returning the syntax tree and a [`SourceMap`] to resolve
+/// its spans.
+pub fn parse_file<T: Parse>(path: impl AsRef<Path>) -> Result<(T, SourceMap)> {
+    let path = path.as_ref();
+    let source_map = SourceMap::from_file(path).map_err(|error| {
+        Error::new(
+            Span::call_site(),
+            format!("failed to read {}: {error}", path.display()),
+        )
+    })?;
+    Ok((source_map.parse()?, source_map))
+}
@@ -0,0 +1,293 @@
+use std::{iter, mem};
+
+use syn::{
+    Error, Expr, LitStr, Result, Token,
+    parse::{Parse, ParseStream},
+};
+
+use crate::helpers::is_format_text;
+
+/// A segment of a format string [`TextNode`]
+#[derive(Debug, PartialEq, Hash)]
+pub enum TextSegment {
+    /// Literal text, with `{{` and `}}` unescaped
+    Text(String),
+
+    /// A placeholder: `{expr}` or `{expr:spec}`
+    Expr {
+        expr: Box<Expr>,
+        /// The format spec after the `:`, e.g. `.2` in `{price:.2}`
+        spec: Option<String>,
+    },
+}
+
+/// A text node: `"Hello, world!"`
+///
+/// A text literal is a format string if it is preceded by a `$`, or if it is inside a node with
+/// the `#[fmt]` attribute. Placeholders can contain any expression and an optional format spec,
+/// and braces are escaped by doubling them like in [`format!`].
+///
+/// The `segments` of a format string are authoritative: if they no longer match `lit`, e.g.
+/// because a placeholder expression was rewritten by a fold, printing and formatting rebuild the
+/// literal from them.
+///
+/// # Example
+/// ```ignore
+/// "Hello, world!"
+/// $"Hello, {user.name}! You have {count} new {{messages}}"
+/// #[fmt]
+/// p { "Total: {price:.2}" }
+/// ```
+#[derive(Debug, PartialEq, Hash)]
+pub struct TextNode {
+    /// The `$` marking a format string
+    pub dollar_token: Option<Token![$]>,
+    pub lit: LitStr,
+    /// The segments of a format string, or `None` for plain text
+    pub segments: Option<Vec<TextSegment>>,
+}
+
+impl TextNode {
+    /// Whether the text is a format string
+    pub fn is_format(&self) -> bool {
+        self.segments.is_some()
+    }
+
+    /// The value of a format string rebuilt from its `segments`, if they no longer match `lit`.
+    /// Placeholder expressions are rendered with `render`.
+    #[cfg(feature = "printing")]
+    pub(crate) fn rebuilt_format_string(&self, render: impl Fn(&Expr) -> String) -> Option<String> {
+        let segments = self.segments.as_ref()?;
+        if parse_segments(&self.lit).is_ok_and(|parsed| &parsed == segments) {
+            return None;
+        }
+
+        let mut value = String::new();
+        for segment in segments {
+            match segment {
+                TextSegment::Text(text) => {
+                    value.push_str(&text.replace('{', "{{").replace('}', "}}"));
+                }
+                TextSegment::Expr { expr, spec } => {
+                    value.push('{');
+                    value.push_str(&render(expr));
+                    if let Some(spec) = spec {
+                        value.push(':');
+                        value.push_str(spec);
+                    }
+                    value.push('}');
+                }
+            }
+        }
+        Some(value)
+    }
+}
+
+impl Parse for TextNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let dollar_token: Option<Token![$]> = input.parse()?;
+        let lit: LitStr = input.parse()?;
+        let segments = (dollar_token.is_some() || is_format_text())
+            .then(|| parse_segments(&lit))
+            .transpose()?;
+        Ok(Self {
+            dollar_token,
+            lit,
+            segments,
+        })
+    }
+}
+
+fn parse_segments(lit: &LitStr) -> Result<Vec<TextSegment>> {
+    let value = lit.value();
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = value.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek().is_some_and(|&(_, next)| next == c) => {
+                chars.next();
+                text.push(c);
+            }
+            '}' => {
+                return Err(Error::new(
+                    lit.span(),
+                    "unmatched `}` in format string, use `}}` to escape it",
+                ));
+            }
+            '{' => {
+                // Find the closing brace, skipping over any nested delimiters and literals of the
+                // expression
+                let start = i + 1;
+                let mut depth = 0;
+                let end = code_bytes(&value[start..]).find_map(|(j, b)| {
+                    match b {
+                        b'}' if depth == 0 => return Some(start + j),
+                        b'{' | b'(' | b'[' => depth += 1,
+                        b'}' | b')' | b']' => depth -= 1,
+                        _ => {}
+                    }
+                    None
+                });
+                let end = end.ok_or_else(|| {
+                    Error::new(
+                        lit.span(),
+                        "unmatched `{` in format string, use `{{` to escape it",
+                    )
+                })?;
+                while chars.next_if(|&(j, _)| j <= end).is_some() {}
+
+                if !text.is_empty() {
+                    segments.push(TextSegment::Text(mem::take(&mut text)));
+                }
+                segments.push(parse_placeholder(lit, &value[i + 1..end])?);
+            }
+            c => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        segments.push(TextSegment::Text(text));
+    }
+    Ok(segments)
+}
+
+/// Parses the contents of a placeholder, `expr` or `expr:spec`
+fn parse_placeholder(lit: &LitStr, placeholder: &str) -> Result<TextSegment> {
+    let trimmed = placeholder.trim_start();
+    if trimmed.is_empty() || (trimmed.starts_with(':') && !trimmed.starts_with("::")) {
+        return Err(Error::new(
+            lit.span(),
+            "empty placeholder in format string, positional arguments are not supported",
+        ));
+    }
+
+    // The spec starts at the first `:` outside of any delimiters and literals that is not part of
+    // a `::` and follows a complete expression, so that the `:` of a closure parameter type or a
+    // loop label is not mistaken for it
+    let bytes = placeholder.as_bytes();
+    let mut depth = 0;
+    let spec = code_bytes(placeholder).find_map(|(i, b)| {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b':' if depth == 0
+                && (i == 0 || bytes[i - 1] != b':')
+                && bytes.get(i + 1) != Some(&b':') =>
+            {
+                let expr = LitStr::new(&placeholder[..i], lit.span()).parse().ok()?;
+                return Some((expr, placeholder[i + 1..].to_string()));
+            }
+            _ => {}
+        }
+        None
+    });
+
+    let (expr, spec) = match spec {
+        Some((expr, spec)) => (expr, Some(spec)),
+        None => (LitStr::new(placeholder, lit.span()).parse()?, None),
+    };
+    Ok(TextSegment::Expr {
+        expr: Box::new(expr),
+        spec,
+    })
+}
+
+/// Iterates over the bytes of rust source that are not part of a string, char or raw string
+/// literal, with their offsets
+fn code_bytes(source: &str) -> impl Iterator<Item = (usize, u8)> + '_ {
+    let bytes = source.as_bytes();
+    let mut i = 0;
+    iter::from_fn(move || {
+        loop {
+            let start = i;
+            let b = *bytes.get(start)?;
+            match literal_end(source, start) {
+                Some(end) => i = end,
+                None => {
+                    i += 1;
+                    return Some((start, b));
+                }
+            }
+        }
+    })
+}
+
+/// Returns the end offset of the string, char or raw string literal starting at `start`. An
+/// unterminated literal extends to the end of the source.
+fn literal_end(source: &str, start: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let is_ident = |i: usize| {
+        bytes
+            .get(i)
+            .is_some_and(|&b| b == b'_' || b.is_ascii_alphanumeric() || !b.is_ascii())
+    };
+
+    match bytes[start] {
+        b'"' => {
+            let mut i = start + 1;
+            while let Some(&b) = bytes.get(i) {
+                match b {
+                    b'\\' => i += 2,
+                    b'"' => return Some(i + 1),
+                    _ => i += 1,
+                }
+            }
+            Some(bytes.len())
+        }
+        // A quote that isn't closed right after one (possibly escaped) char starts a lifetime or
+        // a label
+        b'\'' => {
+            let rest = &source[start + 1..];
+            let len = match rest.strip_prefix('\\') {
+                Some(escaped) => {
+                    let first = escaped.chars().next()?.len_utf8();
+                    1 + first + escaped[first..].find('\'')?
+                }
+                None => rest.chars().next()?.len_utf8(),
+            };
+            rest[len..].starts_with('\'').then_some(start + 1 + len + 1)
+        }
+        // `r"..."`, `r#"..."#`, `br"..."` or `cr"..."`, but not an identifier ending in `r` or a
+        // raw identifier
+        b'r' if start == 0
+            || !is_ident(start - 1)
+            || (matches!(bytes[start - 1], b'b' | b'c')
+                && (start == 1 || !is_ident(start - 2))) =>
+        {
+            let hashes = bytes[start + 1..]
+                .iter()
+                .take_while(|&&b| b == b'#')
+                .count();
+            let quote = start + 1 + hashes;
+            if bytes.get(quote) != Some(&b'"') {
+                return None;
+            }
+            let terminator = format!("\"{}", "#".repeat(hashes));
+            let end = source[quote + 1..]
+                .find(&terminator)
+                .map_or(bytes.len(), |j| quote + 1 + j + terminator.len());
+            Some(end)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(feature = "printing")]
+mod printing {
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+
+    use super::*;
+
+    impl ToTokens for TextNode {
+        fn to_tokens(&self, tokens: &mut TokenStream) {
+            self.dollar_token.to_tokens(tokens);
+            match self.rebuilt_format_string(|expr| expr.to_token_stream().to_string()) {
+                Some(value) => LitStr::new(&value, self.lit.span()).to_tokens(tokens),
+                None => self.lit.to_tokens(tokens),
+            }
+        }
+    }
+}
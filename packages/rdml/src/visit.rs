@@ -0,0 +1,572 @@
+//! Syntax tree traversal to walk a shared borrow of an rdml syntax tree.
+//!
+//! Each method of the [`Visit`] trait is a hook that can be overridden to customize the
+//! behavior when visiting the corresponding type of node. By default, every method
+//! recursively visits the substructure of the input by invoking the right visitor method of
+//! each of its fields.
+//!
+//! Embedded rust syntax (expressions, patterns, paths, ...) is handed to a leaf hook such as
+//! [`Visit::visit_expr`], which does nothing by default.
+//!
+//! # Example
+//!
+//! ```
+//! use rdml::{Element, Nodes, visit::{self, Visit}};
+//!
+//! struct ElementCounter(usize);
+//!
+//! impl<'ast> Visit<'ast> for ElementCounter {
+//!     fn visit_element(&mut self, element: &'ast Element) {
+//!         self.0 += 1;
+//!         visit::visit_element(self, element);
+//!     }
+//! }
+//!
+//! let nodes: Nodes = syn::parse_quote! {
+//!     div { span {} }
+//! };
+//!
+//! let mut counter = ElementCounter(0);
+//! counter.visit_nodes(&nodes);
+//! assert_eq!(counter.0, 2);
+//! ```
+
+use syn::{Expr, Ident, LitStr, Pat, Path};
+
+use crate::{
+    Attribute, AttributeEntry, AttributeName, AttributeNameDirective, AttributeValue, Attributes,
+    Block, ChildrenParams, ClassList, ClassListEntry, Condition, ConditionClause,
+    ConditionalAttributes, Element, ElementClass, ElementId, ElseNode, ExprNode, ForNode, IfNode,
+    LetCondition, LetNode, MacroNode, MatchNode, MatchNodeArm, Node, NodeType, Nodes, RawContent,
+    RawNode, SelectorName, SelectorNameSegment, SlotNode, SpreadAttribute, StyleObject,
+    StyleProperty, TextNode, TextSegment,
+};
+
+/// Syntax tree traversal to walk a shared borrow of an rdml syntax tree.
+///
+/// See the [module documentation](self) for details.
+pub trait Visit<'ast> {
+    fn visit_nodes(&mut self, i: &'ast Nodes) {
+        visit_nodes(self, i);
+    }
+
+    fn visit_block(&mut self, i: &'ast Block) {
+        visit_block(self, i);
+    }
+
+    fn visit_node(&mut self, i: &'ast Node) {
+        visit_node(self, i);
+    }
+
+    fn visit_node_type(&mut self, i: &'ast NodeType) {
+        visit_node_type(self, i);
+    }
+
+    fn visit_element(&mut self, i: &'ast Element) {
+        visit_element(self, i);
+    }
+
+    fn visit_element_class(&mut self, i: &'ast ElementClass) {
+        visit_element_class(self, i);
+    }
+
+    fn visit_element_id(&mut self, i: &'ast ElementId) {
+        visit_element_id(self, i);
+    }
+
+    fn visit_children_params(&mut self, i: &'ast ChildrenParams) {
+        visit_children_params(self, i);
+    }
+
+    fn visit_selector_name(&mut self, i: &'ast SelectorName) {
+        visit_selector_name(self, i);
+    }
+
+    fn visit_attributes(&mut self, i: &'ast Attributes) {
+        visit_attributes(self, i);
+    }
+
+    fn visit_attribute_entry(&mut self, i: &'ast AttributeEntry) {
+        visit_attribute_entry(self, i);
+    }
+
+    fn visit_attribute(&mut self, i: &'ast Attribute) {
+        visit_attribute(self, i);
+    }
+
+    fn visit_spread_attribute(&mut self, i: &'ast SpreadAttribute) {
+        visit_spread_attribute(self, i);
+    }
+
+    fn visit_conditional_attributes(&mut self, i: &'ast ConditionalAttributes) {
+        visit_conditional_attributes(self, i);
+    }
+
+    fn visit_attribute_name(&mut self, i: &'ast AttributeName) {
+        visit_attribute_name(self, i);
+    }
+
+    fn visit_attribute_value(&mut self, i: &'ast AttributeValue) {
+        visit_attribute_value(self, i);
+    }
+
+    fn visit_class_list(&mut self, i: &'ast ClassList) {
+        visit_class_list(self, i);
+    }
+
+    fn visit_class_list_entry(&mut self, i: &'ast ClassListEntry) {
+        visit_class_list_entry(self, i);
+    }
+
+    fn visit_style_object(&mut self, i: &'ast StyleObject) {
+        visit_style_object(self, i);
+    }
+
+    fn visit_style_property(&mut self, i: &'ast StyleProperty) {
+        visit_style_property(self, i);
+    }
+
+    fn visit_attribute_name_directive(&mut self, i: &'ast AttributeNameDirective) {
+        visit_attribute_name_directive(self, i);
+    }
+
+    fn visit_expr_node(&mut self, i: &'ast ExprNode) {
+        visit_expr_node(self, i);
+    }
+
+    fn visit_if_node(&mut self, i: &'ast IfNode) {
+        visit_if_node(self, i);
+    }
+
+    fn visit_else_node(&mut self, i: &'ast ElseNode) {
+        visit_else_node(self, i);
+    }
+
+    fn visit_condition(&mut self, i: &'ast Condition) {
+        visit_condition(self, i);
+    }
+
+    fn visit_let_condition(&mut self, i: &'ast LetCondition) {
+        visit_let_condition(self, i);
+    }
+
+    fn visit_for_node(&mut self, i: &'ast ForNode) {
+        visit_for_node(self, i);
+    }
+
+    fn visit_match_node(&mut self, i: &'ast MatchNode) {
+        visit_match_node(self, i);
+    }
+
+    fn visit_match_node_arm(&mut self, i: &'ast MatchNodeArm) {
+        visit_match_node_arm(self, i);
+    }
+
+    fn visit_let_node(&mut self, i: &'ast LetNode) {
+        visit_let_node(self, i);
+    }
+
+    fn visit_slot_node(&mut self, i: &'ast SlotNode) {
+        visit_slot_node(self, i);
+    }
+
+    fn visit_raw_node(&mut self, i: &'ast RawNode) {
+        visit_raw_node(self, i);
+    }
+
+    fn visit_text_node(&mut self, i: &'ast TextNode) {
+        visit_text_node(self, i);
+    }
+
+    fn visit_macro_node(&mut self, i: &'ast MacroNode) {
+        visit_macro_node(self, i);
+    }
+
+    /// Called for each outer attribute on a [`Node`] or an [`AttributeEntry`], e.g. `#[show]`
+    fn visit_node_attr(&mut self, i: &'ast syn::Attribute) {
+        let _ = i;
+    }
+
+    fn visit_expr(&mut self, i: &'ast Expr) {
+        let _ = i;
+    }
+
+    fn visit_pat(&mut self, i: &'ast Pat) {
+        let _ = i;
+    }
+
+    fn visit_path(&mut self, i: &'ast Path) {
+        let _ = i;
+    }
+
+    fn visit_ident(&mut self, i: &'ast Ident) {
+        let _ = i;
+    }
+
+    fn visit_lit_str(&mut self, i: &'ast LitStr) {
+        let _ = i;
+    }
+}
+
+pub fn visit_nodes<'ast, V>(v: &mut V, node: &'ast Nodes)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.nodes {
+        v.visit_node(it);
+    }
+}
+
+pub fn visit_block<'ast, V>(v: &mut V, node: &'ast Block)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.nodes {
+        v.visit_node(it);
+    }
+}
+
+pub fn visit_node<'ast, V>(v: &mut V, node: &'ast Node)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_node_attr(it);
+    }
+    v.visit_node_type(&node.node);
+}
+
+pub fn visit_node_type<'ast, V>(v: &mut V, node: &'ast NodeType)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        NodeType::Element(element) => v.visit_element(element),
+        NodeType::Text(text_node) => v.visit_text_node(text_node),
+        NodeType::Expr(expr_node) => v.visit_expr_node(expr_node),
+        NodeType::If(if_node) => v.visit_if_node(if_node),
+        NodeType::For(for_node) => v.visit_for_node(for_node),
+        NodeType::Match(match_node) => v.visit_match_node(match_node),
+        NodeType::Let(let_node) => v.visit_let_node(let_node),
+        NodeType::Slot(slot_node) => v.visit_slot_node(slot_node),
+        NodeType::Raw(raw_node) => v.visit_raw_node(raw_node),
+        NodeType::Macro(macro_node) => v.visit_macro_node(macro_node),
+        NodeType::Block(block) => v.visit_block(block),
+    }
+}
+
+pub fn visit_element<'ast, V>(v: &mut V, node: &'ast Element)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_path(&node.path);
+    for it in &node.classes {
+        v.visit_element_class(it);
+    }
+    if let Some(it) = &node.id {
+        v.visit_element_id(it);
+    }
+    if let Some(it) = &node.attributes {
+        v.visit_attributes(it);
+    }
+    if let Some(it) = &node.children_params {
+        v.visit_children_params(it);
+    }
+    if let Some(it) = &node.children {
+        v.visit_block(it);
+    }
+}
+
+pub fn visit_element_class<'ast, V>(v: &mut V, node: &'ast ElementClass)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_selector_name(&node.name);
+}
+
+pub fn visit_element_id<'ast, V>(v: &mut V, node: &'ast ElementId)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_selector_name(&node.name);
+}
+
+pub fn visit_children_params<'ast, V>(v: &mut V, node: &'ast ChildrenParams)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.inputs {
+        v.visit_pat(it);
+    }
+}
+
+pub fn visit_selector_name<'ast, V>(v: &mut V, node: &'ast SelectorName)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.segments {
+        if let SelectorNameSegment::Ident(ident) = it {
+            v.visit_ident(ident);
+        }
+    }
+}
+
+pub fn visit_attributes<'ast, V>(v: &mut V, node: &'ast Attributes)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attributes {
+        v.visit_attribute_entry(it);
+    }
+}
+
+pub fn visit_attribute_entry<'ast, V>(v: &mut V, node: &'ast AttributeEntry)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        AttributeEntry::Attribute(attribute) => v.visit_attribute(attribute),
+        AttributeEntry::Spread(spread) => v.visit_spread_attribute(spread),
+        AttributeEntry::Conditional(conditional) => v.visit_conditional_attributes(conditional),
+    }
+}
+
+pub fn visit_attribute<'ast, V>(v: &mut V, node: &'ast Attribute)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_node_attr(it);
+    }
+    v.visit_attribute_name(&node.name);
+    if let Some(it) = &node.value {
+        v.visit_attribute_value(it);
+    }
+}
+
+pub fn visit_attribute_value<'ast, V>(v: &mut V, node: &'ast AttributeValue)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        AttributeValue::Expr(expr) => v.visit_expr(expr),
+        AttributeValue::Markup(block) => v.visit_block(block),
+        AttributeValue::ClassList(class_list) => v.visit_class_list(class_list),
+        AttributeValue::Style(style) => v.visit_style_object(style),
+    }
+}
+
+pub fn visit_class_list<'ast, V>(v: &mut V, node: &'ast ClassList)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.entries {
+        v.visit_class_list_entry(it);
+    }
+}
+
+pub fn visit_class_list_entry<'ast, V>(v: &mut V, node: &'ast ClassListEntry)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_expr(&node.class);
+    if let Some((_, condition)) = &node.condition {
+        v.visit_expr(condition);
+    }
+}
+
+pub fn visit_style_object<'ast, V>(v: &mut V, node: &'ast StyleObject)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.properties {
+        v.visit_style_property(it);
+    }
+}
+
+pub fn visit_style_property<'ast, V>(v: &mut V, node: &'ast StyleProperty)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_expr(&node.value);
+    if let Some((_, condition)) = &node.condition {
+        v.visit_expr(condition);
+    }
+}
+
+pub fn visit_spread_attribute<'ast, V>(v: &mut V, node: &'ast SpreadAttribute)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_node_attr(it);
+    }
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_conditional_attributes<'ast, V>(v: &mut V, node: &'ast ConditionalAttributes)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.attrs {
+        v.visit_node_attr(it);
+    }
+    v.visit_expr(&node.cond);
+    for it in &node.attributes {
+        v.visit_attribute_entry(it);
+    }
+}
+
+pub fn visit_attribute_name<'ast, V>(v: &mut V, node: &'ast AttributeName)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        AttributeName::Single(path) => v.visit_path(path),
+        AttributeName::Quoted(lit_str) => v.visit_lit_str(lit_str),
+        AttributeName::Directive(directive) => v.visit_attribute_name_directive(directive),
+    }
+}
+
+pub fn visit_attribute_name_directive<'ast, V>(v: &mut V, node: &'ast AttributeNameDirective)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_ident(&node.directive);
+    v.visit_path(&node.path);
+}
+
+pub fn visit_expr_node<'ast, V>(v: &mut V, node: &'ast ExprNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_if_node<'ast, V>(v: &mut V, node: &'ast IfNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_condition(&node.cond);
+    v.visit_block(&node.then_branch);
+    if let Some((_, else_node)) = &node.else_branch {
+        v.visit_else_node(else_node);
+    }
+}
+
+pub fn visit_else_node<'ast, V>(v: &mut V, node: &'ast ElseNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        ElseNode::If(if_node) => v.visit_if_node(if_node),
+        ElseNode::Else(block) => v.visit_block(block),
+    }
+}
+
+pub fn visit_condition<'ast, V>(v: &mut V, node: &'ast Condition)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for it in &node.clauses {
+        match it {
+            ConditionClause::Expr(expr) => v.visit_expr(expr),
+            ConditionClause::Let(let_condition) => v.visit_let_condition(let_condition),
+        }
+    }
+}
+
+pub fn visit_let_condition<'ast, V>(v: &mut V, node: &'ast LetCondition)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_pat(&node.pat);
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_for_node<'ast, V>(v: &mut V, node: &'ast ForNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_pat(&node.pat);
+    v.visit_expr(&node.expr);
+    if let Some((_, guard)) = &node.guard {
+        v.visit_expr(guard);
+    }
+    v.visit_block(&node.body);
+    if let Some((_, block)) = &node.separator {
+        v.visit_block(block);
+    }
+    if let Some((_, block)) = &node.else_branch {
+        v.visit_block(block);
+    }
+}
+
+pub fn visit_match_node<'ast, V>(v: &mut V, node: &'ast MatchNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_expr(&node.expr);
+    for it in &node.arms {
+        v.visit_match_node_arm(it);
+    }
+}
+
+pub fn visit_match_node_arm<'ast, V>(v: &mut V, node: &'ast MatchNodeArm)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_pat(&node.pat);
+    if let Some((_, guard)) = &node.guard {
+        v.visit_expr(guard);
+    }
+    v.visit_node(&node.body);
+}
+
+pub fn visit_let_node<'ast, V>(v: &mut V, node: &'ast LetNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_pat(&node.pat);
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_slot_node<'ast, V>(v: &mut V, node: &'ast SlotNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_path(&node.path);
+    if let Some(it) = &node.attributes {
+        v.visit_attributes(it);
+    }
+    v.visit_block(&node.children);
+}
+
+pub fn visit_raw_node<'ast, V>(v: &mut V, node: &'ast RawNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match &node.content {
+        RawContent::Text(lit_str) => v.visit_lit_str(lit_str),
+        RawContent::Expr(expr_node) => v.visit_expr_node(expr_node),
+    }
+}
+
+pub fn visit_text_node<'ast, V>(v: &mut V, node: &'ast TextNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_lit_str(&node.lit);
+    for it in node.segments.iter().flatten() {
+        if let TextSegment::Expr { expr, .. } = it {
+            v.visit_expr(expr);
+        }
+    }
+}
+
+pub fn visit_macro_node<'ast, V>(v: &mut V, node: &'ast MacroNode)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_path(&node.mac.path);
+}
@@ -0,0 +1,574 @@
+//! Syntax tree traversal to mutate an exclusive borrow of an rdml syntax tree in place.
+//!
+//! Each method of the [`VisitMut`] trait is a hook that can be overridden to customize the
+//! behavior when mutating the corresponding type of node. By default, every method
+//! recursively visits the substructure of the input by invoking the right visitor method of
+//! each of its fields.
+//!
+//! Embedded rust syntax (expressions, patterns, paths, ...) is handed to a leaf hook such as
+//! [`VisitMut::visit_expr_mut`], which does nothing by default.
+//!
+//! # Example
+//!
+//! ```
+//! use rdml::{Nodes, visit_mut::VisitMut};
+//! use syn::LitStr;
+//!
+//! struct Uppercase;
+//!
+//! impl VisitMut for Uppercase {
+//!     fn visit_lit_str_mut(&mut self, lit_str: &mut LitStr) {
+//!         *lit_str = LitStr::new(&lit_str.value().to_uppercase(), lit_str.span());
+//!     }
+//! }
+//!
+//! let mut nodes: Nodes = syn::parse_quote! {
+//!     div { "hello" }
+//! };
+//! Uppercase.visit_nodes_mut(&mut nodes);
+//!
+//! let expected: Nodes = syn::parse_quote! {
+//!     div { "HELLO" }
+//! };
+//! assert_eq!(nodes, expected);
+//! ```
+
+use syn::{Expr, Ident, LitStr, Pat, Path};
+
+use crate::{
+    Attribute, AttributeEntry, AttributeName, AttributeNameDirective, AttributeValue, Attributes,
+    Block, ChildrenParams, ClassList, ClassListEntry, Condition, ConditionClause,
+    ConditionalAttributes, Element, ElementClass, ElementId, ElseNode, ExprNode, ForNode, IfNode,
+    LetCondition, LetNode, MacroNode, MatchNode, MatchNodeArm, Node, NodeType, Nodes, RawContent,
+    RawNode, SelectorName, SelectorNameSegment, SlotNode, SpreadAttribute, StyleObject,
+    StyleProperty, TextNode, TextSegment,
+};
+
+/// Syntax tree traversal to mutate an exclusive borrow of an rdml syntax tree in place.
+///
+/// See the [module documentation](self) for details.
+pub trait VisitMut {
+    fn visit_nodes_mut(&mut self, i: &mut Nodes) {
+        visit_nodes_mut(self, i);
+    }
+
+    fn visit_block_mut(&mut self, i: &mut Block) {
+        visit_block_mut(self, i);
+    }
+
+    fn visit_node_mut(&mut self, i: &mut Node) {
+        visit_node_mut(self, i);
+    }
+
+    fn visit_node_type_mut(&mut self, i: &mut NodeType) {
+        visit_node_type_mut(self, i);
+    }
+
+    fn visit_element_mut(&mut self, i: &mut Element) {
+        visit_element_mut(self, i);
+    }
+
+    fn visit_element_class_mut(&mut self, i: &mut ElementClass) {
+        visit_element_class_mut(self, i);
+    }
+
+    fn visit_element_id_mut(&mut self, i: &mut ElementId) {
+        visit_element_id_mut(self, i);
+    }
+
+    fn visit_children_params_mut(&mut self, i: &mut ChildrenParams) {
+        visit_children_params_mut(self, i);
+    }
+
+    fn visit_selector_name_mut(&mut self, i: &mut SelectorName) {
+        visit_selector_name_mut(self, i);
+    }
+
+    fn visit_attributes_mut(&mut self, i: &mut Attributes) {
+        visit_attributes_mut(self, i);
+    }
+
+    fn visit_attribute_entry_mut(&mut self, i: &mut AttributeEntry) {
+        visit_attribute_entry_mut(self, i);
+    }
+
+    fn visit_attribute_mut(&mut self, i: &mut Attribute) {
+        visit_attribute_mut(self, i);
+    }
+
+    fn visit_spread_attribute_mut(&mut self, i: &mut SpreadAttribute) {
+        visit_spread_attribute_mut(self, i);
+    }
+
+    fn visit_conditional_attributes_mut(&mut self, i: &mut ConditionalAttributes) {
+        visit_conditional_attributes_mut(self, i);
+    }
+
+    fn visit_attribute_name_mut(&mut self, i: &mut AttributeName) {
+        visit_attribute_name_mut(self, i);
+    }
+
+    fn visit_attribute_value_mut(&mut self, i: &mut AttributeValue) {
+        visit_attribute_value_mut(self, i);
+    }
+
+    fn visit_class_list_mut(&mut self, i: &mut ClassList) {
+        visit_class_list_mut(self, i);
+    }
+
+    fn visit_class_list_entry_mut(&mut self, i: &mut ClassListEntry) {
+        visit_class_list_entry_mut(self, i);
+    }
+
+    fn visit_style_object_mut(&mut self, i: &mut StyleObject) {
+        visit_style_object_mut(self, i);
+    }
+
+    fn visit_style_property_mut(&mut self, i: &mut StyleProperty) {
+        visit_style_property_mut(self, i);
+    }
+
+    fn visit_attribute_name_directive_mut(&mut self, i: &mut AttributeNameDirective) {
+        visit_attribute_name_directive_mut(self, i);
+    }
+
+    fn visit_expr_node_mut(&mut self, i: &mut ExprNode) {
+        visit_expr_node_mut(self, i);
+    }
+
+    fn visit_if_node_mut(&mut self, i: &mut IfNode) {
+        visit_if_node_mut(self, i);
+    }
+
+    fn visit_else_node_mut(&mut self, i: &mut ElseNode) {
+        visit_else_node_mut(self, i);
+    }
+
+    fn visit_condition_mut(&mut self, i: &mut Condition) {
+        visit_condition_mut(self, i);
+    }
+
+    fn visit_let_condition_mut(&mut self, i: &mut LetCondition) {
+        visit_let_condition_mut(self, i);
+    }
+
+    fn visit_for_node_mut(&mut self, i: &mut ForNode) {
+        visit_for_node_mut(self, i);
+    }
+
+    fn visit_match_node_mut(&mut self, i: &mut MatchNode) {
+        visit_match_node_mut(self, i);
+    }
+
+    fn visit_match_node_arm_mut(&mut self, i: &mut MatchNodeArm) {
+        visit_match_node_arm_mut(self, i);
+    }
+
+    fn visit_let_node_mut(&mut self, i: &mut LetNode) {
+        visit_let_node_mut(self, i);
+    }
+
+    fn visit_slot_node_mut(&mut self, i: &mut SlotNode) {
+        visit_slot_node_mut(self, i);
+    }
+
+    fn visit_raw_node_mut(&mut self, i: &mut RawNode) {
+        visit_raw_node_mut(self, i);
+    }
+
+    fn visit_text_node_mut(&mut self, i: &mut TextNode) {
+        visit_text_node_mut(self, i);
+    }
+
+    fn visit_macro_node_mut(&mut self, i: &mut MacroNode) {
+        visit_macro_node_mut(self, i);
+    }
+
+    /// Called for each outer attribute on a [`Node`] or an [`AttributeEntry`], e.g. `#[show]`
+    fn visit_node_attr_mut(&mut self, i: &mut syn::Attribute) {
+        let _ = i;
+    }
+
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        let _ = i;
+    }
+
+    fn visit_pat_mut(&mut self, i: &mut Pat) {
+        let _ = i;
+    }
+
+    fn visit_path_mut(&mut self, i: &mut Path) {
+        let _ = i;
+    }
+
+    fn visit_ident_mut(&mut self, i: &mut Ident) {
+        let _ = i;
+    }
+
+    fn visit_lit_str_mut(&mut self, i: &mut LitStr) {
+        let _ = i;
+    }
+}
+
+pub fn visit_nodes_mut<V>(v: &mut V, node: &mut Nodes)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.nodes {
+        v.visit_node_mut(it);
+    }
+}
+
+pub fn visit_block_mut<V>(v: &mut V, node: &mut Block)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.nodes {
+        v.visit_node_mut(it);
+    }
+}
+
+pub fn visit_node_mut<V>(v: &mut V, node: &mut Node)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_node_attr_mut(it);
+    }
+    v.visit_node_type_mut(&mut node.node);
+}
+
+pub fn visit_node_type_mut<V>(v: &mut V, node: &mut NodeType)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        NodeType::Element(element) => v.visit_element_mut(element),
+        NodeType::Text(text_node) => v.visit_text_node_mut(text_node),
+        NodeType::Expr(expr_node) => v.visit_expr_node_mut(expr_node),
+        NodeType::If(if_node) => v.visit_if_node_mut(if_node),
+        NodeType::For(for_node) => v.visit_for_node_mut(for_node),
+        NodeType::Match(match_node) => v.visit_match_node_mut(match_node),
+        NodeType::Let(let_node) => v.visit_let_node_mut(let_node),
+        NodeType::Slot(slot_node) => v.visit_slot_node_mut(slot_node),
+        NodeType::Raw(raw_node) => v.visit_raw_node_mut(raw_node),
+        NodeType::Macro(macro_node) => v.visit_macro_node_mut(macro_node),
+        NodeType::Block(block) => v.visit_block_mut(block),
+    }
+}
+
+pub fn visit_element_mut<V>(v: &mut V, node: &mut Element)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_path_mut(&mut node.path);
+    for it in &mut node.classes {
+        v.visit_element_class_mut(it);
+    }
+    if let Some(it) = &mut node.id {
+        v.visit_element_id_mut(it);
+    }
+    if let Some(it) = &mut node.attributes {
+        v.visit_attributes_mut(it);
+    }
+    if let Some(it) = &mut node.children_params {
+        v.visit_children_params_mut(it);
+    }
+    if let Some(it) = &mut node.children {
+        v.visit_block_mut(it);
+    }
+}
+
+pub fn visit_element_class_mut<V>(v: &mut V, node: &mut ElementClass)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_selector_name_mut(&mut node.name);
+}
+
+pub fn visit_children_params_mut<V>(v: &mut V, node: &mut ChildrenParams)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.inputs {
+        v.visit_pat_mut(it);
+    }
+}
+
+pub fn visit_element_id_mut<V>(v: &mut V, node: &mut ElementId)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_selector_name_mut(&mut node.name);
+}
+
+pub fn visit_selector_name_mut<V>(v: &mut V, node: &mut SelectorName)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.segments {
+        if let SelectorNameSegment::Ident(ident) = it {
+            v.visit_ident_mut(ident);
+        }
+    }
+}
+
+pub fn visit_attributes_mut<V>(v: &mut V, node: &mut Attributes)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attributes {
+        v.visit_attribute_entry_mut(it);
+    }
+}
+
+pub fn visit_attribute_entry_mut<V>(v: &mut V, node: &mut AttributeEntry)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        AttributeEntry::Attribute(attribute) => v.visit_attribute_mut(attribute),
+        AttributeEntry::Spread(spread) => v.visit_spread_attribute_mut(spread),
+        AttributeEntry::Conditional(conditional) => v.visit_conditional_attributes_mut(conditional),
+    }
+}
+
+pub fn visit_attribute_mut<V>(v: &mut V, node: &mut Attribute)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_node_attr_mut(it);
+    }
+    v.visit_attribute_name_mut(&mut node.name);
+    if let Some(it) = &mut node.value {
+        v.visit_attribute_value_mut(it);
+    }
+}
+
+pub fn visit_attribute_value_mut<V>(v: &mut V, node: &mut AttributeValue)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        AttributeValue::Expr(expr) => v.visit_expr_mut(expr),
+        AttributeValue::Markup(block) => v.visit_block_mut(block),
+        AttributeValue::ClassList(class_list) => v.visit_class_list_mut(class_list),
+        AttributeValue::Style(style) => v.visit_style_object_mut(style),
+    }
+}
+
+pub fn visit_class_list_mut<V>(v: &mut V, node: &mut ClassList)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.entries {
+        v.visit_class_list_entry_mut(it);
+    }
+}
+
+pub fn visit_class_list_entry_mut<V>(v: &mut V, node: &mut ClassListEntry)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_expr_mut(&mut node.class);
+    if let Some((_, condition)) = &mut node.condition {
+        v.visit_expr_mut(condition);
+    }
+}
+
+pub fn visit_style_object_mut<V>(v: &mut V, node: &mut StyleObject)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.properties {
+        v.visit_style_property_mut(it);
+    }
+}
+
+pub fn visit_style_property_mut<V>(v: &mut V, node: &mut StyleProperty)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_expr_mut(&mut node.value);
+    if let Some((_, condition)) = &mut node.condition {
+        v.visit_expr_mut(condition);
+    }
+}
+
+pub fn visit_spread_attribute_mut<V>(v: &mut V, node: &mut SpreadAttribute)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_node_attr_mut(it);
+    }
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_conditional_attributes_mut<V>(v: &mut V, node: &mut ConditionalAttributes)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.attrs {
+        v.visit_node_attr_mut(it);
+    }
+    v.visit_expr_mut(&mut node.cond);
+    for it in &mut node.attributes {
+        v.visit_attribute_entry_mut(it);
+    }
+}
+
+pub fn visit_attribute_name_mut<V>(v: &mut V, node: &mut AttributeName)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        AttributeName::Single(path) => v.visit_path_mut(path),
+        AttributeName::Quoted(lit_str) => v.visit_lit_str_mut(lit_str),
+        AttributeName::Directive(directive) => v.visit_attribute_name_directive_mut(directive),
+    }
+}
+
+pub fn visit_attribute_name_directive_mut<V>(v: &mut V, node: &mut AttributeNameDirective)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_ident_mut(&mut node.directive);
+    v.visit_path_mut(&mut node.path);
+}
+
+pub fn visit_expr_node_mut<V>(v: &mut V, node: &mut ExprNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_if_node_mut<V>(v: &mut V, node: &mut IfNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_condition_mut(&mut node.cond);
+    v.visit_block_mut(&mut node.then_branch);
+    if let Some((_, else_node)) = &mut node.else_branch {
+        v.visit_else_node_mut(else_node);
+    }
+}
+
+pub fn visit_else_node_mut<V>(v: &mut V, node: &mut ElseNode)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        ElseNode::If(if_node) => v.visit_if_node_mut(if_node),
+        ElseNode::Else(block) => v.visit_block_mut(block),
+    }
+}
+
+pub fn visit_condition_mut<V>(v: &mut V, node: &mut Condition)
+where
+    V: VisitMut + ?Sized,
+{
+    for it in &mut node.clauses {
+        match it {
+            ConditionClause::Expr(expr) => v.visit_expr_mut(expr),
+            ConditionClause::Let(let_condition) => v.visit_let_condition_mut(let_condition),
+        }
+    }
+}
+
+pub fn visit_let_condition_mut<V>(v: &mut V, node: &mut LetCondition)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_pat_mut(&mut node.pat);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_for_node_mut<V>(v: &mut V, node: &mut ForNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_pat_mut(&mut node.pat);
+    v.visit_expr_mut(&mut node.expr);
+    if let Some((_, guard)) = &mut node.guard {
+        v.visit_expr_mut(guard);
+    }
+    v.visit_block_mut(&mut node.body);
+    if let Some((_, block)) = &mut node.separator {
+        v.visit_block_mut(block);
+    }
+    if let Some((_, block)) = &mut node.else_branch {
+        v.visit_block_mut(block);
+    }
+}
+
+pub fn visit_match_node_mut<V>(v: &mut V, node: &mut MatchNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_expr_mut(&mut node.expr);
+    for it in &mut node.arms {
+        v.visit_match_node_arm_mut(it);
+    }
+}
+
+pub fn visit_match_node_arm_mut<V>(v: &mut V, node: &mut MatchNodeArm)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_pat_mut(&mut node.pat);
+    if let Some((_, guard)) = &mut node.guard {
+        v.visit_expr_mut(guard);
+    }
+    v.visit_node_mut(&mut node.body);
+}
+
+pub fn visit_let_node_mut<V>(v: &mut V, node: &mut LetNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_pat_mut(&mut node.pat);
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_slot_node_mut<V>(v: &mut V, node: &mut SlotNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_path_mut(&mut node.path);
+    if let Some(it) = &mut node.attributes {
+        v.visit_attributes_mut(it);
+    }
+    v.visit_block_mut(&mut node.children);
+}
+
+pub fn visit_raw_node_mut<V>(v: &mut V, node: &mut RawNode)
+where
+    V: VisitMut + ?Sized,
+{
+    match &mut node.content {
+        RawContent::Text(lit_str) => v.visit_lit_str_mut(lit_str),
+        RawContent::Expr(expr_node) => v.visit_expr_node_mut(expr_node),
+    }
+}
+
+pub fn visit_text_node_mut<V>(v: &mut V, node: &mut TextNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_lit_str_mut(&mut node.lit);
+    for it in node.segments.iter_mut().flatten() {
+        if let TextSegment::Expr { expr, .. } = it {
+            v.visit_expr_mut(expr);
+        }
+    }
+}
+
+pub fn visit_macro_node_mut<V>(v: &mut V, node: &mut MacroNode)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_path_mut(&mut node.mac.path);
+}
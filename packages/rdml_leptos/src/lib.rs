@@ -23,6 +23,241 @@
 //! }
 //! # ;
 //! ```
+//! The value of an attribute can be omitted to pass a variable with the same name, so
+//! `Greeting(name)` is the same as `Greeting(name=name)`.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! #[component]
+//! fn Greeting(name: &'static str, title: &'static str) -> impl IntoView {
+//!     rdml! { span(title) { "Hello, " (name) } }
+//! }
+//!
+//! let name = "world";
+//! rdml! { Greeting(name, title="greeting") {} }
+//! # ;
+//! ```
+//!
+//! Classes and an id can be added with a css selector like shorthand after the element name (the
+//! id must be preceded by a space). Shorthand classes are merged with an explicit `class`
+//! attribute.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let active = RwSignal::new(true);
+//!
+//! rdml! {
+//!     div.card.elevated #main {
+//!         span.title(class="large") { "Title" }
+//!         button.btn(class=move || if active.get() { "active" } else { "" }) { "Click" }
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! The `class` attribute also accepts a list of classes, each of which can be made conditional
+//! with `if`. The list is joined into a single reactive class string, and classes that are not
+//! rust identifiers, like `hover:underline`, can be used.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let primary = RwSignal::new(true);
+//! let extra = "rounded-lg";
+//!
+//! rdml! {
+//!     button.btn(class=["hover:underline", "btn-primary" if primary.get(), (extra)]) { "Save" }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! Likewise the `style` attribute accepts css declarations in braces, each of which is lowered to
+//! a `style:` directive so that it is updated on its own. A property with an `if` condition is
+//! only set while the condition is true.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let gap = RwSignal::new(4);
+//! let hidden = RwSignal::new(false);
+//!
+//! rdml! {
+//!     div(style={
+//!         color: "red",
+//!         margin-top: (move || format!("{}px", gap.get())),
+//!         display: "none" if hidden.get(),
+//!     }) {
+//!         "Styled"
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! A component that passes values to its children, like leptos' `Await` or a `let:` binding,
+//! can bind them with a closure-like parameter list before the children.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! #[component]
+//! fn Rows<F, V>(rows: Vec<&'static str>, children: F) -> impl IntoView
+//! where
+//!     F: Fn(usize, &'static str) -> V + 'static,
+//!     V: IntoView,
+//! {
+//!     rows.into_iter()
+//!         .enumerate()
+//!         .map(|(i, row)| children(i, row))
+//!         .collect_view()
+//! }
+//!
+//! rdml! {
+//!     table {
+//!         Rows(rows=vec!["a", "b"]) |i, row: &str| {
+//!             tr { td { (i) } td { (row) } }
+//!         }
+//!     }
+//! }
+//! # ;
+//! ```
+//!
+//! Quoted attribute names can be used for names that are not rust identifiers, and the value of
+//! a quoted or directive attribute can be omitted to set it to `true`.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! rdml! {
+//!     input("type"="checkbox", "aria-label"="Accept", "required", prop:checked)
+//! }
+//! # ;
+//! ```
+//!
+//! An attribute followed by a `?` takes an `Option`, and is left out while it is `None` rather
+//! than rendered empty. The attribute is added and removed reactively if the value is a closure.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let tooltip: Option<&str> = None;
+//! let description = RwSignal::new(Some("help".to_owned()));
+//!
+//! rdml! {
+//!     input(title?=tooltip, "aria-describedby"?=move || description.get())
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! Attributes of an element can be grouped with `if cond { ... }` to only add them while the
+//! condition is true. The condition and the values of the group are evaluated in a closure, so
+//! the attributes are added and removed reactively. An attribute can also be preceded by
+//! `#[cfg(...)]` to only add it when the configuration predicate holds at compile time.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let collapsible = RwSignal::new(true);
+//! let open = RwSignal::new(false);
+//!
+//! rdml! {
+//!     nav(
+//!         #[cfg(debug_assertions)] "data-debug"="nav",
+//!         if collapsible.get() {
+//!             "aria-expanded"=open.get().to_string(),
+//!             "aria-controls"="menu",
+//!             role="menu",
+//!         },
+//!     ) {
+//!         ul #menu { li { "Home" } }
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! A `class` attribute in a group or behind `#[cfg]` is merged with the shorthand classes, which
+//! are kept on their own while the attribute is left out.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let open = RwSignal::new(false);
+//! let menu = move || rdml! { ul.menu(if open.get() { class="open" }) {} }.to_html();
+//! let card = rdml! { div.card(#[cfg(debug_assertions)] class="debug") {} }.to_html();
+//!
+//! assert_eq!(menu(), r#"<ul class="menu"></ul>"#);
+//! open.set(true);
+//! assert_eq!(menu(), r#"<ul class="menu open"></ul>"#);
+//! if cfg!(debug_assertions) {
+//!     assert_eq!(card, r#"<div class="card debug"></div>"#);
+//! } else {
+//!     assert_eq!(card, r#"<div class="card"></div>"#);
+//! }
+//! # });
+//! ```
+//!
+//! Attributes can be forwarded from a collection of attributes with a spread attribute, `..expr`,
+//! which is lowered to leptos' `{..expr}` attribute spreading.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! let external = (leptos::attr::target("_blank"), leptos::attr::rel("noopener"));
+//!
+//! rdml! {
+//!     a(href="https://leptos.dev", ..external) { "Leptos" }
+//! }
+//! # ;
+//! ```
+//!
+//! A component prop can be given markup in braces, which is passed as a closure rendering the
+//! view, e.g. for the `fallback` of `Show`. A block expression can still be passed by wrapping it
+//! in parentheses.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let loaded = RwSignal::new(false);
+//!
+//! rdml! {
+//!     Show(when=move || loaded.get(), fallback={ span.spinner { "Loading" } }) {
+//!         p { "Done" }
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! Elements without children can leave out the braces, ending with a `;` if the next node would
+//! otherwise be mistaken for attributes or children. Void elements like `input` and `br` can't
+//! have children.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! rdml! {
+//!     form {
+//!         input("type"="text");
+//!         br
+//!         button { "Submit" }
+//!     }
+//! }
+//! # ;
+//! ```
+//!
 //! ## Text node
 //!
 //! Quoted text will be interpreted as a text node
@@ -36,6 +271,26 @@
 //! # ;
 //! ```
 //!
+//! Text preceded by a `$`, or any text inside a node with the `#[fmt]` attribute, is a format
+//! string. Each placeholder is rendered as reactive text, so the variables it uses are moved into
+//! a closure.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let count = RwSignal::new(3);
+//! let price = 4.5;
+//!
+//! rdml! {
+//!     p { $"You have {count.get()} new {{messages}}" }
+//!     #[fmt]
+//!     p { "Total: " strong { "{price * 2.0:.2}" } }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
 //! ## Expressions
 //!
 //! Expressions can be any rust expression surrounded by parenthesis.
@@ -51,6 +306,47 @@
 //! # ;
 //! ```
 //!
+//! Macro invocations can be used as nodes directly, with any delimiter, and are passed through
+//! as expressions.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! let name = "Ferris";
+//!
+//! rdml! {
+//!     p { format!("Hello, {name}!") }
+//!     p { concat!["rdml ", "v", 1] }
+//!     view! { <b>"Bold"</b> }
+//! }
+//! # ;
+//! ```
+//!
+//! ## Raw html
+//!
+//! A `raw` node renders html from a string literal or an expression without escaping it. Since
+//! leptos can only set the inner html of an element, the html is wrapped in a `span` with
+//! `display: contents`.
+//!
+//! With the `#[sanitize]` attribute, literal html is checked at compile time and dynamic html is
+//! stripped of disallowed tags and attributes with [`rdml_sanitize::sanitize_html`] at runtime.
+//! Sanitizing dynamic html requires a dependency on `rdml_sanitize`, a small crate without any
+//! dependencies of its own.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! let body = "<p onclick=\"steal()\">From the <b>CMS</b></p>".to_string();
+//!
+//! let html = rdml! {
+//!     raw "<em>Trusted</em> html"
+//!     #[sanitize]
+//!     raw(body)
+//! }
+//! .to_html();
+//! assert!(html.ends_with(r#"<span style="display: contents;"><p>From the <b>CMS</b></p></span>"#));
+//! ```
+//!
 //! ## If blocks
 //!
 //! If blocks can conditionally render certain nodes.
@@ -90,6 +386,31 @@
 //! # ;
 //! ```
 //!
+//! Conditions can also be `if let` pattern matches and let-chains, in both modes. With
+//! `#[show]`, the condition is evaluated once for `when` and once more to bind the pattern in
+//! the rendered branch.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let user = RwSignal::new(Some(("Ferris".to_string(), true)));
+//!
+//! rdml! {
+//!     #[show]
+//!     if let Some((name, true)) = user.get() {
+//!         p { "Welcome back, " (name) }
+//!     } else if let Some((name, _)) = user.get() && !name.is_empty() {
+//!         p { "Welcome, " (name) }
+//!     }
+//!     if let Some((name, _)) = user.get() {
+//!         span { (name) }
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
 //! ## For blocks
 //!
 //! For blocks can render a list of nodes
@@ -124,6 +445,67 @@
 //! # });
 //! ```
 //!
+//! An `else` block is rendered instead when there are no items to render. With `#[key]`, the
+//! list is wrapped in a [`Show`](https://docs.rs/leptos/latest/leptos/control_flow/fn.Show.html)
+//! component whose condition evaluates the expression once more to check whether it is empty.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let items = RwSignal::new(Vec::<String>::new());
+//!
+//! rdml! {
+//!     ul {
+//!         for item in items.get() {
+//!             li { (item) }
+//!         } else {
+//!             li { "No items" }
+//!         }
+//!     }
+//!     #[key(item.clone())]
+//!     for item in items.get() {
+//!         p { (item) }
+//!     } else {
+//!         p { "No items" }
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
+//! Items can be skipped with an `if` guard after the iterable, and a `sep` block is rendered
+//! between each pair of rendered items. The guard sees the item by reference like the predicate of
+//! [`Iterator::filter`], while the body gets it by value. With `#[key]`, an item is rendered again
+//! when it becomes or stops being the first item.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! # leptos::reactive::owner::Owner::new().with(|| {
+//! let tags = RwSignal::new(vec![("rust", true), ("draft", false), ("web", true)]);
+//!
+//! rdml! {
+//!     p {
+//!         for (tag, visible) in tags.get() if *visible {
+//!             span { (tag) }
+//!         } sep {
+//!             ", "
+//!         }
+//!     }
+//!     #[key(tag.to_string())]
+//!     for (tag, visible) in tags.get() if *visible {
+//!         span { (tag) }
+//!     } sep {
+//!         " / "
+//!     } else {
+//!         "No tags"
+//!     }
+//! }
+//! # ;
+//! # });
+//! ```
+//!
 //! ## Match blocks
 //!
 //! You can also use match statements for control flow (this always generates a move closure with a rust match block)
@@ -148,6 +530,58 @@
 //! # ;
 //! ```
 //!
+//! ## Slots
+//!
+//! A `slot` node passes a region of markup to a leptos `#[slot]` of the component it is a child
+//! of. Attributes of the slot are passed as the props of the slot struct.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! #[slot]
+//! struct Header {
+//!     #[prop(optional)]
+//!     sticky: bool,
+//!     children: ChildrenFn,
+//! }
+//!
+//! #[component]
+//! fn Layout(header: Header, children: Children) -> impl IntoView {
+//!     let class = if header.sticky { "sticky" } else { "" };
+//!     rdml! {
+//!         header(class) { ((header.children)()) }
+//!         main { (children()) }
+//!     }
+//! }
+//!
+//! rdml! {
+//!     Layout {
+//!         slot Header(sticky=true) { h1 { "Title" } }
+//!         p { "Body" }
+//!     }
+//! }
+//! # ;
+//! ```
+//!
+//! ## Let nodes
+//!
+//! A `let` node binds a pattern like a rust `let` statement. The bindings are in scope for the
+//! nodes after it in the same block.
+//!
+//! ```
+//! # use rdml_leptos::rdml;
+//! # use leptos::prelude::*;
+//! let name = "Jane Doe";
+//!
+//! rdml! {
+//!     let (first, last) = name.split_once(' ').unwrap();
+//!     let initials: String = format!("{}{}", &first[..1], &last[..1]);
+//!     span { (first) }
+//!     span { (initials) }
+//! }
+//! # ;
+//! ```
+//!
 //! ## With attributes
 //!
 //! Most nodes and blocks can have the `#[with([stmt])]` attribute applied to enter a new scope with the given statment.
@@ -178,10 +612,15 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt, quote, quote_spanned};
 use rdml::{
-    Attribute, AttributeName, Block, Element, ElseNode, ExprNode, ForNode, IfNode, MatchNode,
-    MatchNodeArm, Node, NodeType, Nodes,
+    Attribute, AttributeEntry, AttributeName, AttributeValue, Block, ClassList,
+    ConditionalAttributes, Element, ElseNode, ExprNode, ForNode, IfNode, LetNode, MacroNode,
+    MatchNode, MatchNodeArm, Node, NodeType, Nodes, RawContent, RawNode, Recovered, SlotNode,
+    StyleObject, TextNode, TextSegment,
+};
+use syn::{
+    Expr, ExprLit, Ident, Lit, LitStr, Result, Stmt, ext::IdentExt, parse::Parser,
+    parse_macro_input, spanned::Spanned, token::Paren,
 };
-use syn::{Expr, Result, Stmt, parse_macro_input, spanned::Spanned, token::Paren};
 
 fn generate_attribute_name(attr_name: &AttributeName) -> Result<TokenStream> {
     match attr_name {
@@ -191,55 +630,529 @@ fn generate_attribute_name(attr_name: &AttributeName) -> Result<TokenStream> {
             path,
             colon_token,
         }) => Ok(quote! {#directive #colon_token #path }),
-        AttributeName::Quoted(_lit_str) => todo!("Implement quoted attributes"),
+        AttributeName::Quoted(lit_str) => {
+            // Leptos accepts `-` separated identifiers, e.g. `aria-label`
+            let segments = lit_str
+                .value()
+                .split('-')
+                .map(|segment| {
+                    let mut ident = Ident::parse_any.parse_str(segment).map_err(|_| {
+                        syn::Error::new(
+                            lit_str.span(),
+                            "quoted attribute names must be `-` separated identifiers",
+                        )
+                    })?;
+                    ident.set_span(lit_str.span());
+                    Ok(ident)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote_spanned! {lit_str.span()=> #(#segments)-* })
+        }
     }
 }
 
+/// Generates the span and value of an attribute, filling in the value if it was omitted
+fn generate_attribute_value(attr: &Attribute, name: &TokenStream) -> Result<(Span, TokenStream)> {
+    let span = match &attr.eq_token {
+        Some(eq_token) => eq_token.span(),
+        None => attr.name.span(),
+    };
+    let value = match &attr.value {
+        // Leptos leaves out attributes whose value is `None`, the value of an optional attribute
+        // only has to be checked to be an `Option`. A closure is passed as is, so that the
+        // attribute is added and removed reactively.
+        Some(AttributeValue::Expr(expr))
+            if attr.is_optional() && !matches!(expr, Expr::Closure(_)) =>
+        {
+            quote_spanned! {expr.span()=>
+                ::core::convert::identity::<::core::option::Option<_>>(#expr)
+            }
+        }
+        Some(AttributeValue::Expr(expr)) => expr.to_token_stream(),
+        Some(value) if attr.is_optional() => {
+            return Err(syn::Error::new_spanned(
+                value,
+                "the value of an optional attribute must be an expression",
+            ));
+        }
+        // Markup is passed as a closure rendering the view, like the `fallback` of `Show`
+        Some(AttributeValue::Markup(block)) => {
+            let nodes = generate_block(block)?;
+            quote_spanned! {block.brace_token.span.join()=> move || view! { #nodes } }
+        }
+        Some(AttributeValue::ClassList(class_list)) => generate_class_list(class_list, ""),
+        Some(AttributeValue::Style(style)) => {
+            return Err(syn::Error::new_spanned(
+                style,
+                "style objects can only be used as the value of a `style` attribute",
+            ));
+        }
+        // A punned attribute uses the variable with the same name as the attribute
+        None if attr.is_punned() => name.clone(),
+        None => quote_spanned! {span=> true },
+    };
+    Ok((span, value))
+}
+
+/// Generates a closure joining the classes of a class list, with the classes of the selector
+/// shorthand prepended. Only the conditions and parenthesized classes are reactive, so the class
+/// string is only recomputed when a signal they read changes.
+fn generate_class_list(class_list: &ClassList, classes: &str) -> TokenStream {
+    let span = class_list.bracket_token.span.join();
+    let shorthand = (!classes.is_empty()).then(|| {
+        quote_spanned! {span=> rdml_classes.push(::std::string::String::from(#classes)); }
+    });
+    let entries = class_list.entries.iter().map(|entry| {
+        let class = &entry.class;
+        let push = quote_spanned! {class.span()=>
+            rdml_classes.push(::std::string::ToString::to_string(&#class));
+        };
+        match &entry.condition {
+            Some((if_token, condition)) => {
+                quote_spanned! {if_token.span=> if #condition { #push } }
+            }
+            None => push,
+        }
+    });
+
+    quote_spanned! {span=>
+        move || {
+            let mut rdml_classes = ::std::vec::Vec::<::std::string::String>::new();
+            #shorthand
+            #(#entries)*
+            rdml_classes.retain(|rdml_class| !rdml_class.is_empty());
+            rdml_classes.join(" ")
+        }
+    }
+}
+
+/// Generates a `style:` directive for each property of a style object, so that each property is
+/// updated on its own. A conditional property is removed while its condition is false.
+fn generate_style_object(style: &StyleObject) -> TokenStream {
+    let mut directives = TokenStream::new();
+    for property in &style.properties {
+        let span = property.name.span();
+        let style_token = Ident::new("style", span);
+        let segments = property
+            .name
+            .segments
+            .iter()
+            .map(|segment| Ident::new(&segment.unraw().to_string(), segment.span()));
+        let value = &property.value;
+        let value = match &property.condition {
+            Some((if_token, condition)) => quote_spanned! {if_token.span=>
+                move || if #condition {
+                    ::core::option::Option::Some(#value)
+                } else {
+                    ::core::option::Option::None
+                }
+            },
+            None => value.to_token_stream(),
+        };
+        directives.append_all(quote_spanned! {span=> #style_token:#(#segments)-* = {#value} });
+    }
+    directives
+}
+
 fn generate_attribute(attr: &Attribute) -> Result<TokenStream> {
+    if let Some(AttributeValue::Style(style)) = &attr.value
+        && !attr.is_optional()
+    {
+        return Ok(generate_style_object(style));
+    }
+
     let name = generate_attribute_name(&attr.name)?;
-    let eq_token = &attr.eq_token;
-    let value = &attr.value;
+    let (span, value) = generate_attribute_value(attr, &name)?;
 
-    Ok(quote_spanned! {eq_token.span()=>
-        #name #eq_token {#value}
+    Ok(quote_spanned! {span=>
+        #name = {#value}
     })
 }
 
+fn attribute_has_name(attr: &Attribute, name: &str) -> bool {
+    match &attr.name {
+        AttributeName::Single(path) => path.is_ident(name),
+        AttributeName::Quoted(lit_str) => lit_str.value() == name,
+        AttributeName::Directive(_) => false,
+    }
+}
+
+/// Generates an explicit `class` attribute with the classes of the selector shorthand prepended
+fn generate_merged_class(attr: &Attribute, classes: &str) -> Result<TokenStream> {
+    let name = generate_attribute_name(&attr.name)?;
+    let (span, value) = generate_attribute_value(attr, &name)?;
+
+    match &attr.value {
+        Some(AttributeValue::Expr(Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }))) => {
+            let classes = format!("{classes} {}", lit_str.value());
+            return Ok(quote_spanned! {span=> #name = #classes });
+        }
+        Some(AttributeValue::ClassList(class_list)) => {
+            let value = generate_class_list(class_list, classes);
+            return Ok(quote_spanned! {span=> #name = {#value} });
+        }
+        _ => {}
+    }
+
+    // Any other value is rendered to a string, which stays reactive for reactive values
+    Ok(quote_spanned! {span=>
+        #name = {
+            let rdml_class = ::leptos::tachys::html::class::IntoClass::into_cloneable_owned(#value);
+            move || {
+                let mut classes = ::std::string::String::from(#classes);
+                classes.push(' ');
+                ::leptos::tachys::html::class::IntoClass::to_html(
+                    ::core::clone::Clone::clone(&rdml_class),
+                    &mut classes,
+                );
+                classes
+            }
+        }
+    })
+}
+
+/// Generates an attribute of a conditional group, whose value is `None` while any of the
+/// conditions is false. The value is computed in the same closure as the conditions. A `class`
+/// attribute is merged with the classes of the selector shorthand instead, which are kept while
+/// the conditions are false.
+fn generate_conditional_attribute(
+    attr: &Attribute,
+    conds: &[&Expr],
+    classes: &str,
+) -> Result<TokenStream> {
+    if let AttributeName::Directive(directive) = &attr.name {
+        return Err(syn::Error::new_spanned(
+            directive,
+            "directives can't be used in conditional attribute groups",
+        ));
+    }
+    if let Some(
+        value @ (AttributeValue::Markup(_)
+        | AttributeValue::ClassList(_)
+        | AttributeValue::Style(_)),
+    ) = &attr.value
+    {
+        return Err(syn::Error::new_spanned(
+            value,
+            "only expression values can be used in conditional attribute groups",
+        ));
+    }
+
+    let name = generate_attribute_name(&attr.name)?;
+    let (span, value) = generate_attribute_value(attr, &name)?;
+    let cond = match conds {
+        [cond] => cond.to_token_stream(),
+        conds => quote! { #((#conds))&&* },
+    };
+    if !classes.is_empty() && attribute_has_name(attr, "class") {
+        return Ok(quote_spanned! {span=>
+            #name = {
+                move || {
+                    let mut rdml_classes = ::std::string::String::from(#classes);
+                    if #cond {
+                        rdml_classes.push(' ');
+                        ::leptos::tachys::html::class::IntoClass::to_html(#value, &mut rdml_classes);
+                    }
+                    rdml_classes
+                }
+            }
+        });
+    }
+    Ok(quote_spanned! {span=>
+        #name = {
+            move || if #cond {
+                ::core::option::Option::Some(#value)
+            } else {
+                ::core::option::Option::None
+            }
+        }
+    })
+}
+
+/// Generates the attributes of a conditional group, nested groups add their condition to `conds`
+fn generate_conditional_attributes(
+    conditional: &ConditionalAttributes,
+    conds: &[&Expr],
+    classes: &str,
+) -> Result<TokenStream> {
+    let mut conds = conds.to_vec();
+    conds.push(&conditional.cond);
+
+    let mut attributes = TokenStream::new();
+    for entry in &conditional.attributes {
+        let tokens = match entry {
+            AttributeEntry::Attribute(attr) => {
+                generate_conditional_attribute(attr, &conds, classes)?
+            }
+            AttributeEntry::Spread(spread) => {
+                return Err(syn::Error::new_spanned(
+                    spread,
+                    "spread attributes can't be used in conditional attribute groups",
+                ));
+            }
+            AttributeEntry::Conditional(nested) => {
+                generate_conditional_attributes(nested, &conds, classes)?
+            }
+        };
+        attributes.append_all(generate_cfg_entry(entry, tokens)?);
+    }
+    Ok(attributes)
+}
+
+/// Wraps the generated attributes of an entry with its `#[cfg]` attributes. The attributes are
+/// spread from a tuple, whose only element is removed when the predicate is false.
+fn generate_cfg_entry(entry: &AttributeEntry, tokens: TokenStream) -> Result<TokenStream> {
+    let attrs = entry.attrs();
+    let Some(first) = attrs.first() else {
+        return Ok(tokens);
+    };
+    if let Some(attr) = attrs.iter().find(|attr| !attr.path().is_ident("cfg")) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "only `#[cfg]` attributes can be used on attributes",
+        ));
+    }
+
+    let span = first.pound_token.span;
+    let attributes = match entry {
+        // Leptos can't spread attributes inside of an attribute bundle
+        AttributeEntry::Spread(spread) => spread.expr.to_token_stream(),
+        _ => quote_spanned! {span=> view! { <{..} #tokens /> } },
+    };
+    Ok(quote_spanned! {span=> {..(#(#attrs)* #attributes,)} })
+}
+
+/// Generates the attributes of an entry, a `class` attribute is merged with the classes of the
+/// selector shorthand
+fn generate_attribute_entry(entry: &AttributeEntry, classes: &str) -> Result<TokenStream> {
+    let tokens = match entry {
+        AttributeEntry::Attribute(attr)
+            if !classes.is_empty() && attribute_has_name(attr, "class") =>
+        {
+            generate_merged_class(attr, classes)?
+        }
+        AttributeEntry::Attribute(attr) => generate_attribute(attr)?,
+        AttributeEntry::Spread(spread) => {
+            let dot2_token = &spread.dot2_token;
+            let expr = &spread.expr;
+            quote_spanned! {dot2_token.span()=> {#dot2_token #expr} }
+        }
+        AttributeEntry::Conditional(conditional) => {
+            generate_conditional_attributes(conditional, &[], classes)?
+        }
+    };
+    generate_cfg_entry(entry, tokens)
+}
+
+/// Finds the `#[cfg]` predicates of the first `class` attribute of an entry, including those of
+/// the conditional groups it is nested in. Returns `None` if the entry has no `class` attribute.
+fn class_cfg_predicates(entry: &AttributeEntry) -> Result<Option<Vec<TokenStream>>> {
+    let nested = match entry {
+        AttributeEntry::Attribute(attr) if attribute_has_name(attr, "class") => Some(Vec::new()),
+        AttributeEntry::Attribute(_) | AttributeEntry::Spread(_) => None,
+        AttributeEntry::Conditional(conditional) => {
+            let mut found = None;
+            for entry in &conditional.attributes {
+                found = class_cfg_predicates(entry)?;
+                if found.is_some() {
+                    break;
+                }
+            }
+            found
+        }
+    };
+    let Some(mut predicates) = nested else {
+        return Ok(None);
+    };
+    for attr in entry.attrs() {
+        if attr.path().is_ident("cfg") {
+            predicates.push(attr.meta.require_list()?.tokens.clone());
+        }
+    }
+    Ok(Some(predicates))
+}
+
+/// Components and slots take their attributes as props, which can't be left out conditionally
+fn check_unconditional(entry: &AttributeEntry) -> Result<()> {
+    if let Some(attr) = entry.attrs().first() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "conditional attributes can only be used on elements",
+        ));
+    }
+    if let AttributeEntry::Conditional(conditional) = entry {
+        return Err(syn::Error::new_spanned(
+            conditional.if_token,
+            "conditional attributes can only be used on elements",
+        ));
+    }
+    Ok(())
+}
+
+/// HTML elements that can't have any children
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
 fn generate_element(el: &Element) -> Result<TokenStream> {
     let path = &el.path;
+    // Leptos treats elements whose name starts with an uppercase letter as components
+    let is_component = path.segments.last().is_some_and(|segment| {
+        segment
+            .ident
+            .to_string()
+            .starts_with(|c: char| c.is_ascii_uppercase())
+    });
 
-    let attributes = el
-        .attributes
-        .as_ref()
-        .map(|attributes| {
-            let builders = attributes
-                .attributes
-                .iter()
-                .map(generate_attribute)
-                .collect::<Result<Vec<_>>>()?;
-            Result::Ok(quote! {#(#builders )*})
-        })
-        .transpose()?;
+    let classes = el
+        .classes
+        .iter()
+        .map(|class| class.name.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut merged_class = false;
+    let mut builders = Vec::new();
+    for entry in el.attributes.iter().flat_map(|it| &it.attributes) {
+        if is_component {
+            check_unconditional(entry)?;
+        }
+        match entry {
+            AttributeEntry::Attribute(Attribute {
+                value: Some(AttributeValue::Markup(block)),
+                ..
+            }) if !is_component => {
+                return Err(syn::Error::new_spanned(
+                    block,
+                    "markup attribute values can only be passed to components",
+                ));
+            }
+            AttributeEntry::Attribute(attr)
+                if let Some(id) = &el.id
+                    && attribute_has_name(attr, "id") =>
+            {
+                return Err(syn::Error::new_spanned(
+                    &attr.name,
+                    format!(
+                        "duplicate id, the element already has the id `#{}`",
+                        id.name
+                    ),
+                ));
+            }
+            entry => {
+                // The classes of the selector shorthand are merged into the `class` attribute,
+                // and are set on their own when its `#[cfg]` predicates are false
+                if !classes.is_empty()
+                    && let Some(predicates) = class_cfg_predicates(entry)?
+                {
+                    merged_class = true;
+                    if !predicates.is_empty() {
+                        builders.push(quote_spanned! {entry.span()=>
+                            {..(
+                                #[cfg(not(all(#(#predicates),*)))]
+                                view! { <{..} class=#classes /> },
+                            )}
+                        });
+                    }
+                }
+                builders.push(generate_attribute_entry(entry, &classes)?);
+            }
+        }
+    }
 
-    let children = generate_block(&el.children)?;
+    let mut attributes = TokenStream::new();
+    if !classes.is_empty() && !merged_class {
+        let span = el.classes[0].dot_token.span;
+        attributes.append_all(quote_spanned! {span=> class=#classes });
+    }
+    if let Some(id) = &el.id {
+        let name = id.name.to_string();
+        attributes.append_all(quote_spanned! {id.pound_token.span=> id=#name });
+    }
+    attributes.append_all(builders);
 
-    Ok(quote_spanned! {path.span()=>
-        <#path #attributes>
-            #children
-        </#path>
-    })
+    if let Some(params) = &el.children_params {
+        if !is_component {
+            return Err(syn::Error::new_spanned(
+                params,
+                "children parameters can only be passed to components",
+            ));
+        }
+
+        let let_token = Ident::new("let", params.or1_token.span);
+        let inputs = &params.inputs;
+        attributes.append_all(quote_spanned! {params.or1_token.span=> #let_token(#inputs) });
+    }
+
+    let is_void = path
+        .get_ident()
+        .is_some_and(|ident| VOID_ELEMENTS.contains(&ident.to_string().as_str()));
+
+    match &el.children {
+        Some(children) if is_void && !children.nodes.is_empty() => Err(syn::Error::new_spanned(
+            children,
+            format!(
+                "`{}` is a void element and can't have children",
+                path.to_token_stream()
+            ),
+        )),
+        Some(children) if !is_void => {
+            let children = generate_block(children)?;
+            Ok(quote_spanned! {path.span()=>
+                <#path #attributes>
+                    #children
+                </#path>
+            })
+        }
+        _ => Ok(quote_spanned! {path.span()=>
+            <#path #attributes />
+        }),
+    }
 }
 
-fn generate_block(block: &Block) -> Result<TokenStream> {
-    let nodes = block
-        .nodes
+/// Collects every result, combining all of the errors instead of stopping at the first one.
+fn collect_all<T>(results: impl IntoIterator<Item = Result<T>>) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut error: Option<syn::Error> = None;
+    for result in results {
+        match (result, &mut error) {
+            (Ok(value), _) => values.push(value),
+            (Err(err), Some(error)) => error.combine(err),
+            (Err(err), None) => error = Some(err),
+        }
+    }
+    match error {
+        Some(error) => Err(error),
+        None => Ok(values),
+    }
+}
+
+fn generate_nodes(nodes: &[Node]) -> Result<TokenStream> {
+    // A let node is lowered together with the nodes after it, which are in its scope
+    let let_index = nodes
         .iter()
-        .map(generate_node)
-        .collect::<Result<Vec<_>>>()?;
+        .position(|node| matches!(node.node, NodeType::Let(_)));
+    let (nodes, scoped) = nodes.split_at(let_index.unwrap_or(nodes.len()));
+
+    let mut results = nodes.iter().map(generate_node).collect::<Vec<_>>();
+    if let [node, rest @ ..] = scoped
+        && let NodeType::Let(let_node) = &node.node
+    {
+        results.push(generate_let_node(let_node, &node.attrs, rest));
+    }
+    let nodes = collect_all(results)?;
 
     Ok(quote! { #(#nodes)* })
 }
 
+fn generate_block(block: &Block) -> Result<TokenStream> {
+    generate_nodes(&block.nodes)
+}
+
 fn generate_if_node(if_node: &IfNode, attrs: &[syn::Attribute]) -> Result<TokenStream> {
     if attrs
         .iter()
@@ -256,6 +1169,22 @@ fn generate_if_node(if_node: &IfNode, attrs: &[syn::Attribute]) -> Result<TokenS
                 Result::Ok(quote_spanned! {else_token.span()=> fallback=(move || view! { #else_branch })})
             }).transpose()?;
             let then_branch = generate_block(&node.then_branch)?;
+            if node.cond.has_let() {
+                // `when` can't bind the pattern, so the condition is matched again in the
+                // rendered branch
+                return Ok(quote_spanned! {if_token.span()=>
+                    <Show
+                        when=(move || #if_token #cond { true } else { false })
+                        #fallback
+                    >
+                        {move || #if_token #cond {
+                            view! { #then_branch }.into_any()
+                        } else {
+                            view! { }.into_any()
+                        }}
+                    </Show>
+                });
+            }
             Ok(quote_spanned! {if_token.span()=>
                 <Show
                     when=(move || #cond)
@@ -294,7 +1223,7 @@ fn generate_if_node(if_node: &IfNode, attrs: &[syn::Attribute]) -> Result<TokenS
                     else_branch = if_node.else_branch.as_ref();
                 }
                 ElseNode::Else(block) => {
-                    let block = generate_block(&block)?;
+                    let block = generate_block(block)?;
                     result.append_all(quote_spanned! {else_token.span()=>
                         #else_token {
                             view! { #block }.into_any()
@@ -328,19 +1257,129 @@ fn generate_for_node(for_node: &ForNode, attrs: &[syn::Attribute]) -> Result<Tok
     let expr = &for_node.expr;
     let body = generate_block(&for_node.body)?;
 
+    let separator = for_node
+        .separator
+        .as_ref()
+        .map(|(sep_token, block)| Result::Ok((sep_token, generate_block(block)?)))
+        .transpose()?;
+    let else_branch = for_node
+        .else_branch
+        .as_ref()
+        .map(|(else_token, block)| Result::Ok((else_token, generate_block(block)?)))
+        .transpose()?;
+
     if let Some(key_attr) = key_attr {
         let key: Expr = key_attr.parse_args().unwrap(); // TODO: expose error
-        Ok(quote_spanned! {for_token.span()=>
-            <For
-                each=(move || { #expr })
-                key=(move |#pat| { #key })
-                children=(move |#pat| { view! { #body } })
-            />
-        })
+
+        let each = match &for_node.guard {
+            Some((if_token, guard)) => {
+                quote_spanned! {if_token.span()=>
+                    ::std::iter::Iterator::filter(
+                        ::std::iter::IntoIterator::into_iter(#expr),
+                        |rdml_item| {
+                            #[allow(unused_variables)]
+                            let #pat = rdml_item;
+                            #guard
+                        },
+                    )
+                }
+            }
+            None => quote_spanned! {for_token.span()=> (#expr) },
+        };
+
+        let for_component = match separator {
+            // Items are enumerated to render the separator before all but the first item, which
+            // is part of the key so that an item is rendered again when it becomes the first
+            Some((sep_token, separator)) => quote_spanned! {sep_token.span()=>
+                <For
+                    each=(move || ::std::iter::Iterator::enumerate(::std::iter::IntoIterator::into_iter(#each)))
+                    key=(move |(rdml_index, #pat)| (*rdml_index == 0, { #key }))
+                    children=(move |(rdml_index, #pat)| view! {
+                        {(rdml_index > 0).then(|| view! { #separator })}
+                        #body
+                    })
+                />
+            },
+            None => quote_spanned! {for_token.span()=>
+                <For
+                    each=(move || { #each })
+                    key=(move |#pat| { #key })
+                    children=(move |#pat| { view! { #body } })
+                />
+            },
+        };
+        match else_branch {
+            // `For` has no fallback, so the list is wrapped in a `Show` that checks whether the
+            // iterator yields any items
+            Some((else_token, else_branch)) => Ok(quote_spanned! {else_token.span()=>
+                <Show
+                    when=(move || ::std::iter::IntoIterator::into_iter(#each).next().is_some())
+                    fallback=(move || view! { #else_branch })
+                >
+                    #for_component
+                </Show>
+            }),
+            None => Ok(for_component),
+        }
     } else {
-        Ok(quote_spanned! {for_token.span()=>
-            {(#expr).into_iter().map(|#pat| view! { #body }).collect::<Vec<_>>()}
-        })
+        let items = if for_node.guard.is_none() && separator.is_none() {
+            quote_spanned! {for_token.span()=>
+                (#expr).into_iter().map(|#pat| view! { #body }).collect::<Vec<_>>()
+            }
+        } else {
+            let separator = separator.map(|(sep_token, separator)| {
+                quote_spanned! {sep_token.span()=>
+                    if !rdml_items.is_empty() {
+                        rdml_items.push(view! { #separator }.into_any());
+                    }
+                }
+            });
+            let push = quote_spanned! {for_token.span()=>
+                #separator
+                rdml_items.push(view! { #body }.into_any());
+            };
+            let each = match &for_node.guard {
+                // The guard sees the item by reference, like in the keyed `filter`
+                Some((if_token, guard)) => quote_spanned! {if_token.span()=>
+                    #for_token rdml_item in #expr {
+                        #if_token {
+                            #[allow(unused_variables)]
+                            let #pat = &rdml_item;
+                            #guard
+                        } {
+                            let #pat = rdml_item;
+                            #push
+                        }
+                    }
+                },
+                None => quote_spanned! {for_token.span()=>
+                    #for_token #pat in #expr {
+                        #push
+                    }
+                },
+            };
+            quote_spanned! {for_token.span()=>
+                {
+                    let mut rdml_items = Vec::new();
+                    #each
+                    rdml_items
+                }
+            }
+        };
+
+        match else_branch {
+            Some((else_token, else_branch)) => Ok(quote_spanned! {else_token.span()=>
+                {
+                    let rdml_items = #items;
+                    if rdml_items.is_empty() {
+                        view! { #else_branch }.into_any()
+                    } else {
+                        rdml_items.into_any()
+                    }
+                }
+            }),
+            None => Ok(quote_spanned! {for_token.span()=> { #items } }),
+        }
     }
 }
 
@@ -372,6 +1411,102 @@ fn generate_match_node(node: &MatchNode) -> Result<TokenStream> {
     })
 }
 
+fn generate_let_node(
+    let_node: &LetNode,
+    attrs: &[syn::Attribute],
+    rest: &[Node],
+) -> Result<TokenStream> {
+    if let Some(attr) = attrs.first() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "attributes are not supported on `let` nodes",
+        ));
+    }
+
+    let LetNode {
+        let_token,
+        pat,
+        eq_token,
+        expr,
+        semi_token,
+    } = let_node;
+    let rest = generate_nodes(rest)?;
+    Ok(quote_spanned! {let_token.span()=>
+        {{
+            #let_token #pat #eq_token #expr #semi_token
+            view! { #rest }
+        }}
+    })
+}
+
+fn generate_slot_node(slot_node: &SlotNode) -> Result<TokenStream> {
+    let path = &slot_node.path;
+    let attributes = collect_all(
+        slot_node
+            .attributes
+            .iter()
+            .flat_map(|it| &it.attributes)
+            .map(|entry| {
+                check_unconditional(entry)?;
+                generate_attribute_entry(entry, "")
+            }),
+    )?;
+    let slot = Ident::new("slot", slot_node.slot_token.span);
+    let children = generate_block(&slot_node.children)?;
+
+    Ok(quote_spanned! {path.span()=>
+        <#path #slot #(#attributes)*>
+            #children
+        </#path>
+    })
+}
+
+fn generate_text_node(text_node: &TextNode) -> TokenStream {
+    let Some(segments) = &text_node.segments else {
+        return text_node.lit.to_token_stream();
+    };
+
+    let span = text_node.lit.span();
+    let segments = segments.iter().map(|segment| match segment {
+        TextSegment::Text(text) => LitStr::new(text, span).to_token_stream(),
+        TextSegment::Expr { expr, spec } => {
+            let format = match spec {
+                Some(spec) => format!("{{:{spec}}}"),
+                None => "{}".to_string(),
+            };
+            let format = LitStr::new(&format, span);
+            quote_spanned! {span=>{ move || ::std::format!(#format, #expr) }}
+        }
+    });
+    quote! { #(#segments)* }
+}
+
+fn generate_raw_node(raw_node: &RawNode, sanitize: bool) -> Result<TokenStream> {
+    let html = match &raw_node.content {
+        RawContent::Text(lit_str) => {
+            if sanitize && let Err(violation) = rdml_sanitize::check_html(&lit_str.value()) {
+                return Err(syn::Error::new_spanned(lit_str, violation));
+            }
+            lit_str.to_token_stream()
+        }
+        RawContent::Expr(expr_node) => {
+            let ExprNode { expr, paren_token } = &**expr_node;
+            if sanitize {
+                quote_spanned! {paren_span(paren_token)=>{
+                    let rdml_html = #expr;
+                    ::rdml_sanitize::sanitize_html(::core::convert::AsRef::<str>::as_ref(&rdml_html))
+                }}
+            } else {
+                quote_spanned! {paren_span(paren_token)=>{ #expr }}
+            }
+        }
+    };
+
+    Ok(quote_spanned! {raw_node.raw_token.span=>
+        <span style="display: contents" inner_html=#html />
+    })
+}
+
 fn paren_span(paren: &Paren) -> Span {
     let mut tokens = quote! {};
     paren.surround(&mut tokens, |_| {});
@@ -379,15 +1514,32 @@ fn paren_span(paren: &Paren) -> Span {
 }
 
 fn generate_node(node: &Node) -> Result<TokenStream> {
+    let sanitize_attr = node
+        .attrs
+        .iter()
+        .find(|attr| attr.path().get_ident().is_some_and(|id| id == "sanitize"));
+    if let Some(attr) = sanitize_attr
+        && !matches!(node.node, NodeType::Raw(_))
+    {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`#[sanitize]` can only be used on `raw` nodes",
+        ));
+    }
+
     let node_tokens = match &node.node {
         NodeType::Element(element) => generate_element(element)?,
-        NodeType::Text(lit_str) => lit_str.to_token_stream(),
+        NodeType::Text(text_node) => generate_text_node(text_node),
         NodeType::Expr(ExprNode { expr, paren_token }) => {
-            quote_spanned! {paren_span(&paren_token)=>{ #expr }}
+            quote_spanned! {paren_span(paren_token)=>{ #expr }}
         }
         NodeType::If(if_node) => generate_if_node(if_node, &node.attrs)?,
         NodeType::For(for_node) => generate_for_node(for_node, &node.attrs)?,
         NodeType::Match(match_node) => generate_match_node(match_node)?,
+        NodeType::Let(let_node) => generate_let_node(let_node, &node.attrs, &[])?,
+        NodeType::Slot(slot_node) => generate_slot_node(slot_node)?,
+        NodeType::Raw(raw_node) => generate_raw_node(raw_node, sanitize_attr.is_some())?,
+        NodeType::Macro(MacroNode { mac }) => quote_spanned! {mac.span()=>{ #mac }},
         NodeType::Block(block) => generate_block(block)?,
     };
 
@@ -402,8 +1554,7 @@ fn generate_node(node: &Node) -> Result<TokenStream> {
         let span = node
             .attrs
             .iter()
-            .filter(|attr| attr.path().get_ident().is_some_and(|id| id == "with"))
-            .next()
+            .find(|attr| attr.path().get_ident().is_some_and(|id| id == "with"))
             // Ok because with_attr is non-empty
             .unwrap()
             .path()
@@ -422,19 +1573,27 @@ fn generate_node(node: &Node) -> Result<TokenStream> {
 
 #[proc_macro]
 pub fn rdml(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let nodes = parse_macro_input!(tokens as Nodes);
+    // Parse with error recovery so that every syntax error is reported in one build
+    let Recovered {
+        value: nodes,
+        errors,
+    } = parse_macro_input!(tokens as Recovered<Nodes>);
 
-    let nodes = nodes
-        .nodes
-        .iter()
-        .map(generate_node)
-        .collect::<Result<Vec<_>>>();
+    let nodes = collect_all(
+        errors
+            .into_iter()
+            .map(Err)
+            .chain([generate_nodes(&nodes.nodes)]),
+    );
 
     match nodes {
         Ok(nodes) => quote! {{
             #[allow(unused_variables)]
             #[allow(unused_parens)]
             #[allow(unused_braces)]
+            // An empty block node, like a `{}` match arm, is rendered as a `()` view with the span
+            // of its braces, which clippy would report as a unit expression in the user's code
+            #[allow(clippy::unused_unit)]
             {
                 ::leptos::prelude::view! {
                     #(#nodes)*
@@ -0,0 +1,417 @@
+//! An allowlist based html sanitizer for rdml raw html nodes.
+//!
+//! Backends can use [`check_html`] to validate literal html at compile time, and
+//! [`sanitize_html`] to strip disallowed markup from dynamic html at runtime. This crate has no
+//! dependencies, so the code generated for dynamic html can call it without pulling a parser into
+//! the application.
+//!
+//! Only common formatting tags and a few attributes are kept. The tags `script`, `style`,
+//! `iframe` and the like are removed together with their content, other disallowed tags are
+//! removed while keeping their content. `href` and `src` attributes can only contain relative
+//! urls or `http`, `https` and `mailto` urls.
+//!
+//! # Example
+//!
+//! ```
+//! use rdml_sanitize::{check_html, sanitize_html};
+//!
+//! let html = r#"<b onclick="steal()">Hi</b><script>steal()</script>"#;
+//! assert_eq!(sanitize_html(html), "<b>Hi</b>");
+//! assert_eq!(
+//!     check_html(html).unwrap_err().to_string(),
+//!     "the `onclick` attribute is not allowed on `<b>`",
+//! );
+//! ```
+
+use std::fmt::{self, Display};
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Disallowed tags whose content is removed along with them
+const REMOVED_CONTENT_TAGS: &[&str] = &[
+    "iframe", "math", "noscript", "object", "script", "style", "svg", "template", "textarea",
+    "title",
+];
+
+const ALLOWED_ATTRIBUTES: &[&str] = &[
+    "alt", "cite", "class", "colspan", "height", "href", "lang", "rowspan", "src", "title", "width",
+];
+
+const URL_ATTRIBUTES: &[&str] = &["cite", "href", "src"];
+
+const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Disallowed markup found by [`check_html`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A disallowed tag
+    Tag(String),
+    /// A disallowed attribute of an allowed tag
+    Attribute { tag: String, name: String },
+    /// A url attribute with a disallowed scheme, e.g. `javascript:`
+    Url { tag: String, name: String },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Tag(tag) => write!(f, "`<{tag}>` tags are not allowed"),
+            Violation::Attribute { tag, name } => {
+                write!(f, "the `{name}` attribute is not allowed on `<{tag}>`")
+            }
+            Violation::Url { tag, name } => {
+                write!(
+                    f,
+                    "the `{name}` attribute of `<{tag}>` has a disallowed url"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Returns the first disallowed tag or attribute in `html`, if any.
+pub fn check_html(html: &str) -> Result<(), Violation> {
+    let mut violation = None;
+    sanitize(html, &mut |it| {
+        violation.get_or_insert(it);
+    });
+    violation.map_or(Ok(()), Err)
+}
+
+/// Removes every disallowed tag and attribute from `html`.
+pub fn sanitize_html(html: &str) -> String {
+    sanitize(html, &mut |_| {})
+}
+
+fn sanitize(html: &str, on_violation: &mut impl FnMut(Violation)) -> String {
+    let mut result = String::with_capacity(html.len());
+    // The removed tag whose content is being skipped
+    let mut removing: Option<String> = None;
+
+    for token in (Tokenizer { rest: html }) {
+        if let Some(tag) = &removing {
+            if matches!(&token, Token::End(name) if name == tag) {
+                removing = None;
+            }
+            continue;
+        }
+
+        match token {
+            Token::Text(text) => result.push_str(&text.replace('<', "&lt;")),
+            Token::Comment => {}
+            Token::Start {
+                name,
+                attributes,
+                self_closing,
+            } => {
+                if !ALLOWED_TAGS.contains(&name.as_str()) {
+                    if REMOVED_CONTENT_TAGS.contains(&name.as_str()) && !self_closing {
+                        removing = Some(name.clone());
+                    }
+                    on_violation(Violation::Tag(name));
+                    continue;
+                }
+
+                result.push('<');
+                result.push_str(&name);
+                for (attribute, value) in attributes {
+                    if !ALLOWED_ATTRIBUTES.contains(&attribute.as_str()) {
+                        on_violation(Violation::Attribute {
+                            tag: name.clone(),
+                            name: attribute,
+                        });
+                        continue;
+                    }
+
+                    let value = value.unwrap_or_default();
+                    if URL_ATTRIBUTES.contains(&attribute.as_str()) && !is_allowed_url(&value) {
+                        on_violation(Violation::Url {
+                            tag: name.clone(),
+                            name: attribute,
+                        });
+                        continue;
+                    }
+
+                    result.push_str(&format!(
+                        " {attribute}=\"{}\"",
+                        value.replace('"', "&quot;").replace('<', "&lt;")
+                    ));
+                }
+                result.push('>');
+            }
+            Token::End(name) => {
+                if ALLOWED_TAGS.contains(&name.as_str()) {
+                    result.push_str(&format!("</{name}>"));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Whether `url` is relative or uses an allowed scheme. Character references are not decoded,
+/// so any `&` before the path of the url is rejected.
+fn is_allowed_url(url: &str) -> bool {
+    let url = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect::<String>();
+    let prefix = url.split(['/', '?', '#']).next().unwrap_or_default();
+    match prefix.split_once(':') {
+        Some((scheme, _)) => ALLOWED_URL_SCHEMES
+            .iter()
+            .any(|allowed| scheme.eq_ignore_ascii_case(allowed)),
+        None => !prefix.contains('&'),
+    }
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    /// A comment, doctype or processing instruction
+    Comment,
+    Start {
+        name: String,
+        attributes: Vec<(String, Option<String>)>,
+        self_closing: bool,
+    },
+    End(String),
+}
+
+/// A lenient html tokenizer, names are lowercased
+struct Tokenizer<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    fn skip_past(&mut self, pattern: &str) {
+        let len = self
+            .rest
+            .find(pattern)
+            .map_or(self.rest.len(), |i| i + pattern.len());
+        self.advance(len);
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.advance(self.rest.len() - self.rest.trim_start().len());
+    }
+
+    /// Takes a tag or attribute name
+    fn name(&mut self) -> String {
+        let len = self
+            .rest
+            .find(|c: char| c.is_ascii_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(self.rest.len());
+        self.advance(len).to_ascii_lowercase()
+    }
+
+    fn attribute_value(&mut self) -> String {
+        match self.rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                self.advance(1);
+                let len = self.rest.find(quote).unwrap_or(self.rest.len());
+                let value = self.advance(len).to_string();
+                self.advance(self.rest.len().min(1));
+                value
+            }
+            _ => {
+                let len = self
+                    .rest
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(self.rest.len());
+                self.advance(len).to_string()
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> Token<'a> {
+        let name = self.name();
+        let mut attributes = Vec::new();
+        let mut self_closing = false;
+        loop {
+            self.skip_whitespace();
+            match self.rest.chars().next() {
+                None => break,
+                Some('>') => {
+                    self.advance(1);
+                    break;
+                }
+                Some('/') => {
+                    self.advance(1);
+                    self_closing = true;
+                }
+                Some(_) => {
+                    self_closing = false;
+                    let attribute = self.name();
+                    if attribute.is_empty() {
+                        // A stray `=`
+                        self.advance(1);
+                        continue;
+                    }
+                    self.skip_whitespace();
+                    let value = self.rest.starts_with('=').then(|| {
+                        self.advance(1);
+                        self.skip_whitespace();
+                        self.attribute_value()
+                    });
+                    attributes.push((attribute, value));
+                }
+            }
+        }
+        Token::Start {
+            name,
+            attributes,
+            self_closing,
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let starts_name = |s: &str| s.starts_with(|c: char| c.is_ascii_alphabetic());
+        if let Some(after) = self.rest.strip_prefix("</")
+            && starts_name(after)
+        {
+            self.advance(2);
+            let name = self.name();
+            self.skip_past(">");
+            Some(Token::End(name))
+        } else if self.rest.starts_with("<!--") {
+            self.skip_past("-->");
+            Some(Token::Comment)
+        } else if self.rest.starts_with("<!") || self.rest.starts_with("<?") {
+            self.skip_past(">");
+            Some(Token::Comment)
+        } else if let Some(after) = self.rest.strip_prefix('<')
+            && starts_name(after)
+        {
+            self.advance(1);
+            Some(self.start_tag())
+        } else {
+            // Text up to the next `<`, which is kept as text if it doesn't start a tag
+            let skip = usize::from(self.rest.starts_with('<'));
+            let len = self.rest[skip..]
+                .find('<')
+                .map_or(self.rest.len(), |i| i + skip);
+            Some(Token::Text(self.advance(len)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_sanitize() {
+        // (html, sanitized, allowed)
+        let cases = [
+            ("plain & <b>bold</b> text", "plain & <b>bold</b> text", true),
+            ("a < b <3", "a &lt; b &lt;3", true),
+            ("<P CLASS=note>x</P>", "<p class=\"note\">x</p>", true),
+            (
+                "<img src='/a.png' onerror=alert(1) />",
+                "<img src=\"/a.png\">",
+                false,
+            ),
+            ("<a href=\"javascript:alert(1)\">x</a>", "<a>x</a>", false),
+            (
+                "<a href=\"java&#115;cript:alert(1)\">x</a>",
+                "<a>x</a>",
+                false,
+            ),
+            (
+                "<a href=\"https://a.b/?c=1&d=2\">x</a>",
+                "<a href=\"https://a.b/?c=1&d=2\">x</a>",
+                true,
+            ),
+            ("<script>alert('<b>')</script>after", "after", false),
+            ("<form><input>kept</form>", "kept", false),
+            ("<!-- comment --><!DOCTYPE html>héllo", "héllo", true),
+            (
+                "<b title='say \"hi\"'>",
+                "<b title=\"say &quot;hi&quot;\">",
+                true,
+            ),
+        ];
+        for (html, sanitized, allowed) in cases {
+            assert_eq!(sanitize_html(html), sanitized, "sanitizing {html:?}");
+            assert_eq!(check_html(html).is_ok(), allowed, "checking {html:?}");
+        }
+
+        assert_eq!(
+            check_html("<p><script></script><b onclick=x>"),
+            Err(Violation::Tag("script".to_string()))
+        );
+        assert_eq!(
+            check_html("<a href=\"data:text/html,x\">"),
+            Err(Violation::Url {
+                tag: "a".to_string(),
+                name: "href".to_string()
+            })
+        );
+    }
+}